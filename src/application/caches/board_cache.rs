@@ -0,0 +1,170 @@
+use std::{ collections::HashMap, sync::{ Arc, Mutex }, time::{ Duration, Instant } };
+
+use crate::domain::value_objects::board_page_model::BoardPageModel;
+
+/// In-process cache of quest board pages, keyed by everything that shapes a page.
+///
+/// Every use case that writes quests, crews or progress calls `invalidate`.
+/// Other instances are not told about those writes, `ttl` bounds how stale
+/// their pages can get.
+pub struct BoardCache {
+    ttl: Duration,
+    capacity: usize,
+    state: Mutex<BoardCacheState>,
+}
+
+#[derive(Default)]
+struct BoardCacheState {
+    /// Bumped by every invalidation, see `BoardCache::insert`.
+    generation: u64,
+    entries: HashMap<String, BoardCacheEntry>,
+}
+
+struct BoardCacheEntry {
+    stored_at: Instant,
+    page: Arc<BoardPageModel>,
+}
+
+impl BoardCache {
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity,
+            state: Mutex::new(BoardCacheState::default()),
+        }
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Take this before querying and hand it to `insert` afterwards.
+    pub fn generation(&self) -> u64 {
+        self.lock().generation
+    }
+
+    pub fn get(&self, key: &str) -> Option<Arc<BoardPageModel>> {
+        let state = self.lock();
+
+        state.entries
+            .get(key)
+            .filter(|entry| entry.stored_at.elapsed() < self.ttl)
+            .map(|entry| Arc::clone(&entry.page))
+    }
+
+    /// Stores a page loaded while the cache was at `generation`. A page that
+    /// raced with a write is dropped rather than cached past the invalidation.
+    pub fn insert(&self, key: String, generation: u64, page: Arc<BoardPageModel>) {
+        let mut state = self.lock();
+
+        if state.generation != generation || self.capacity == 0 {
+            return;
+        }
+
+        if state.entries.len() >= self.capacity && !state.entries.contains_key(&key) {
+            state.entries.retain(|_, entry| entry.stored_at.elapsed() < self.ttl);
+        }
+
+        if state.entries.len() >= self.capacity && !state.entries.contains_key(&key) {
+            let oldest = state.entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.stored_at)
+                .map(|(key, _)| key.clone());
+
+            if let Some(oldest) = oldest {
+                state.entries.remove(&oldest);
+            }
+        }
+
+        state.entries.insert(key, BoardCacheEntry { stored_at: Instant::now(), page });
+    }
+
+    pub fn invalidate(&self) {
+        let mut state = self.lock();
+
+        state.generation += 1;
+        state.entries.clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BoardCacheState> {
+        // The state is only ever left consistent, a panic elsewhere does not poison it.
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(total: i64) -> Arc<BoardPageModel> {
+        Arc::new(BoardPageModel { items: Vec::new(), next_cursor: None, total })
+    }
+
+    fn cache(capacity: usize) -> BoardCache {
+        BoardCache::new(Duration::from_secs(60), capacity)
+    }
+
+    #[test]
+    fn serves_a_page_stored_at_the_current_generation() {
+        let cache = cache(4);
+
+        cache.insert("board".to_string(), cache.generation(), page(1));
+
+        assert_eq!(cache.get("board").map(|page| page.total), Some(1));
+        assert!(cache.get("other").is_none());
+    }
+
+    #[test]
+    fn invalidate_drops_stored_pages() {
+        let cache = cache(4);
+
+        cache.insert("board".to_string(), cache.generation(), page(1));
+        cache.invalidate();
+
+        assert!(cache.get("board").is_none());
+    }
+
+    #[test]
+    fn drops_a_page_loaded_before_an_invalidation() {
+        let cache = cache(4);
+        let generation = cache.generation();
+
+        cache.invalidate();
+        cache.insert("board".to_string(), generation, page(1));
+
+        assert!(cache.get("board").is_none());
+    }
+
+    #[test]
+    fn expires_pages_after_the_ttl() {
+        let cache = BoardCache::new(Duration::from_millis(20), 4);
+
+        cache.insert("board".to_string(), cache.generation(), page(1));
+        std::thread::sleep(Duration::from_millis(40));
+
+        assert!(cache.get("board").is_none());
+    }
+
+    #[test]
+    fn zero_capacity_disables_the_cache() {
+        let cache = cache(0);
+
+        cache.insert("board".to_string(), cache.generation(), page(1));
+
+        assert!(cache.get("board").is_none());
+    }
+
+    #[test]
+    fn evicts_the_oldest_page_when_full() {
+        let cache = cache(2);
+
+        cache.insert("first".to_string(), cache.generation(), page(1));
+        std::thread::sleep(Duration::from_millis(2));
+        cache.insert("second".to_string(), cache.generation(), page(2));
+        cache.insert("third".to_string(), cache.generation(), page(3));
+
+        assert!(cache.get("first").is_none());
+        assert!(cache.get("second").is_some());
+        assert!(cache.get("third").is_some());
+    }
+}
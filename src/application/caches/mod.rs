@@ -0,0 +1 @@
+pub mod board_cache;
\ No newline at end of file
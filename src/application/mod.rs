@@ -1 +1,3 @@
+pub mod caches;
+pub mod policies;
 pub mod usecases;
\ No newline at end of file
@@ -0,0 +1 @@
+pub mod quest_ownership;
\ No newline at end of file
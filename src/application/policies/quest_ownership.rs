@@ -0,0 +1,18 @@
+use crate::domain::{
+    entities::quests::QuestEntity,
+    errors::{ AppError, AppResult },
+    value_objects::ids::GuildCommanderId,
+};
+
+/// Only the commander who posted a quest may edit, remove or move it along
+/// the journey ledger, or decide on its applications. `guild_commander_id`
+/// must come from the verified token.
+pub fn ensure_quest_owner(quest: &QuestEntity, guild_commander_id: GuildCommanderId) -> AppResult<()> {
+    if quest.guild_commander_id != guild_commander_id {
+        return Err(
+            AppError::Forbidden(format!("Quest {} belongs to another guild commander", quest.id))
+        );
+    }
+
+    Ok(())
+}
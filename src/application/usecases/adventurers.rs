@@ -1,10 +1,13 @@
 use std::sync::Arc;
 
-use anyhow::Result;
-
-use crate::{domain::{
+use crate::{config::config_loader, domain::{
+    errors::AppResult,
     repositories::adventurers::AdventurersRepository,
-    value_objects::adventurer_model::RegisterAdventurerModel,
+    value_objects::{
+        adventurer_model::RegisterAdventurerModel,
+        ids::AdventurerId,
+        progression::AdventurerProgressModel,
+    },
 }, infrastructure::argon2_hashing};
 
 pub struct AdventurersUseCase<T> where T: AdventurersRepository + Send + Sync {
@@ -21,7 +24,7 @@ impl<T> AdventurersUseCase<T> where T: AdventurersRepository + Send + Sync {
     pub async fn register(
         &self,
         mut register_adventurer_model: RegisterAdventurerModel
-    ) -> Result<i32> {
+    ) -> AppResult<AdventurerId> {
         let hashed_password = argon2_hashing::hash(register_adventurer_model.password.clone())?;
         register_adventurer_model.password = hashed_password;
 
@@ -31,4 +34,12 @@ impl<T> AdventurersUseCase<T> where T: AdventurersRepository + Send + Sync {
 
         Ok(adventurer_id)
     }
+
+    pub async fn progress(&self, adventurer_id: AdventurerId) -> AppResult<AdventurerProgressModel> {
+        let progression = config_loader::load()?.progression.rules();
+
+        let adventurer = self.adventurers_repository.find_by_id(adventurer_id).await?;
+
+        Ok(progression.progress(adventurer.level, adventurer.xp, adventurer.gold))
+    }
 }
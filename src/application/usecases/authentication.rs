@@ -1,151 +1,284 @@
 use std::sync::Arc;
 
-use anyhow::Result;
 use chrono::{Duration, Utc};
+use uuid::Uuid;
 
 use crate::{
-    config::config_loader, domain::repositories::{
-        adventurers::AdventurersRepository,
-        guild_commanders::GuildCommanderRepository,
-    }, infrastructure::{argon2_hashing, jwt_authentication::{ self, authentication_model::LoginModel, generate_token, jwt_model::{Claims, Passport, Roles} }}
+    config::config_loader, domain::{
+        entities::sessions::{ AddSessionEntity, SessionEntity },
+        errors::{ AppError, AppResult },
+        repositories::{
+            adventurers::AdventurersRepository,
+            guild_commanders::GuildCommanderRepository,
+            sessions::SessionsRepository,
+        },
+    }, infrastructure::{argon2_hashing, jwt_authentication::{ self, authentication_model::LoginModel, generate_token, hash_token, jwt_model::{Claims, Passport, Roles} }}
 };
 
-pub struct AuthenticationUseCase<T1, T2>
-    where T1: AdventurersRepository + Send + Sync, T2: GuildCommanderRepository + Send + Sync {
+pub struct AuthenticationUseCase<T1, T2, T3>
+    where
+        T1: AdventurersRepository + Send + Sync,
+        T2: GuildCommanderRepository + Send + Sync,
+        T3: SessionsRepository + Send + Sync
+{
     adventurers_repository: Arc<T1>,
     guild_commanders_repository: Arc<T2>,
+    sessions_repository: Arc<T3>,
 }
 
-impl<T1, T2> AuthenticationUseCase<T1, T2>
-    where T1: AdventurersRepository + Send + Sync, T2: GuildCommanderRepository + Send + Sync
+impl<T1, T2, T3> AuthenticationUseCase<T1, T2, T3>
+    where
+        T1: AdventurersRepository + Send + Sync,
+        T2: GuildCommanderRepository + Send + Sync,
+        T3: SessionsRepository + Send + Sync
 {
-    pub fn new(adventurers_repository: Arc<T1>, guild_commanders_repository: Arc<T2>) -> Self {
+    pub fn new(
+        adventurers_repository: Arc<T1>,
+        guild_commanders_repository: Arc<T2>,
+        sessions_repository: Arc<T3>
+    ) -> Self {
         Self {
             adventurers_repository,
             guild_commanders_repository,
+            sessions_repository,
         }
     }
 
-    pub async fn adventurers_login(&self, login_model: LoginModel) -> Result<Passport> {
+    pub async fn adventurers_login(&self, login_model: LoginModel) -> AppResult<Passport> {
         let secret_env = config_loader::load()?.adventurers_secret;
-        
-        let adventurer = self.adventurers_repository.find_by_username(login_model.username.clone()).await?;
+
+        let adventurer = self.adventurers_repository
+            .find_by_username(login_model.username.clone()).await
+            .map_err(|err| match err {
+                AppError::NotFound(_) => AppError::Unauthorized("Invalid username or password".to_string()),
+                err => err,
+            })?;
 
         let original_password = adventurer.password;
         let login_password = login_model.password;
-        println!("Original: {}, Login: {}", original_password, login_password);
 
         if !argon2_hashing::verify(login_password, original_password)? {
-            return  Err(anyhow::anyhow!("Invalid password"));
-        };
-
-        let access_token_claims = Claims {
-            sub: adventurer.id.to_string(),
-            role: Roles::Adventurer,
-            exp: (Utc::now() + Duration::days(1)).timestamp() as usize,
-            iat: Utc::now().timestamp() as usize,
-        };
-
-        let refresh_token_claims = Claims {
-            sub: adventurer.id.to_string(),
-            role: Roles::Adventurer,
-            exp: (Utc::now() + Duration::days(7)).timestamp() as usize,
-            iat: Utc::now().timestamp() as usize,
+            return Err(AppError::Unauthorized("Invalid username or password".to_string()));
         };
 
-        let access_token = generate_token(secret_env.secret, &access_token_claims)?;
-        let refresh_token = generate_token(secret_env.refresh_secret, &refresh_token_claims)?;
-
-        Ok(Passport {
-            access_token,
-            refresh_token,
-        })
+        self.start_session(adventurer.id.into(), Roles::Adventurer, secret_env.secret, secret_env.refresh_secret).await
     }
 
-    pub async fn adventurers_refresh_token(&self, refresh_token: String) -> Result<Passport>  {
+    pub async fn adventurers_refresh_token(&self, refresh_token: String) -> AppResult<Passport>  {
         let secret_env = config_loader::load()?.adventurers_secret;
 
-        let claims = jwt_authentication::verify_token(secret_env.refresh_secret.clone(), refresh_token)?;
+        self.rotate_session(refresh_token, Roles::Adventurer, secret_env.secret, secret_env.refresh_secret).await
+    }
 
-        let access_token_claims = Claims {
-            sub: claims.sub.clone(),
-            role: Roles::Adventurer,
-            exp: (Utc::now() + Duration::days(1)).timestamp() as usize,
-            iat: Utc::now().timestamp() as usize,
-        };
+    pub async fn adventurers_logout(&self, refresh_token: String) -> AppResult<()> {
+        let secret_env = config_loader::load()?.adventurers_secret;
 
-        let refresh_token_claims = Claims {
-            sub: claims.sub.clone(),
-            role: Roles::Adventurer,
-            exp: claims.exp,
-            iat: Utc::now().timestamp() as usize,
-        };
+        self.end_session(refresh_token, Roles::Adventurer, secret_env.refresh_secret).await
+    }
 
-        let access_token = generate_token(secret_env.secret, &access_token_claims)?;
-        let refresh_token = generate_token(secret_env.refresh_secret, &refresh_token_claims)?;
+    pub async fn adventurers_logout_all(&self, refresh_token: String) -> AppResult<()> {
+        let secret_env = config_loader::load()?.adventurers_secret;
 
-        Ok(Passport {
-            access_token,
-            refresh_token,
-        })
+        self.end_all_sessions(refresh_token, Roles::Adventurer, secret_env.refresh_secret).await
     }
 
-    pub async fn guild_commanders_login(&self, login_model: LoginModel) -> Result<Passport> {
+    pub async fn guild_commanders_login(&self, login_model: LoginModel) -> AppResult<Passport> {
         let secret_env = config_loader::load()?.guild_commanders_secret;
-        
-        let guild_commander = self.guild_commanders_repository.find_by_username(login_model.username.clone()).await?;
+
+        let guild_commander = self.guild_commanders_repository
+            .find_by_username(login_model.username.clone()).await
+            .map_err(|err| match err {
+                AppError::NotFound(_) => AppError::Unauthorized("Invalid username or password".to_string()),
+                err => err,
+            })?;
 
         let original_password = guild_commander.password;
         let login_password = login_model.password;
 
         if !argon2_hashing::verify(login_password, original_password)? {
-            return  Err(anyhow::anyhow!("Invalid password"));
+            return Err(AppError::Unauthorized("Invalid username or password".to_string()));
         };
 
-        let access_token_claims = Claims {
-            sub: guild_commander.id.to_string(),
-            role: Roles::GuildCommander,
-            exp: (Utc::now() + Duration::days(1)).timestamp() as usize,
-            iat: Utc::now().timestamp() as usize,
-        };
+        self.start_session(guild_commander.id.into(), Roles::GuildCommander, secret_env.secret, secret_env.refresh_secret).await
+    }
 
-        let refresh_token_claims = Claims {
-            sub: guild_commander.id.to_string(),
-            role: Roles::GuildCommander,
-            exp: (Utc::now() + Duration::days(7)).timestamp() as usize,
-            iat: Utc::now().timestamp() as usize,
-        };
+    pub async fn guild_commanders_refresh_token(&self, refresh_token: String) -> AppResult<Passport>  {
+        let secret_env = config_loader::load()?.guild_commanders_secret;
 
-        let access_token = generate_token(secret_env.secret, &access_token_claims)?;
-        let refresh_token = generate_token(secret_env.refresh_secret, &refresh_token_claims)?;
+        self.rotate_session(refresh_token, Roles::GuildCommander, secret_env.secret, secret_env.refresh_secret).await
+    }
 
-        Ok(Passport {
-            access_token,
-            refresh_token,
-        })
+    pub async fn guild_commanders_logout(&self, refresh_token: String) -> AppResult<()> {
+        let secret_env = config_loader::load()?.guild_commanders_secret;
+
+        self.end_session(refresh_token, Roles::GuildCommander, secret_env.refresh_secret).await
     }
 
-    pub async fn guild_commanders_refresh_token(&self, refresh_token: String) -> Result<Passport>  {
+    pub async fn guild_commanders_logout_all(&self, refresh_token: String) -> AppResult<()> {
         let secret_env = config_loader::load()?.guild_commanders_secret;
 
-        let claims = jwt_authentication::verify_token(secret_env.refresh_secret.clone(), refresh_token)?;
+        self.end_all_sessions(refresh_token, Roles::GuildCommander, secret_env.refresh_secret).await
+    }
+
+    async fn start_session(
+        &self,
+        subject_id: i32,
+        role: Roles,
+        secret: String,
+        refresh_secret: String
+    ) -> AppResult<Passport> {
+        let expires_at = Utc::now() + Duration::days(7);
+
+        let passport = Self::sign_passport(
+            subject_id.to_string(),
+            role.clone(),
+            expires_at.timestamp() as usize,
+            secret,
+            refresh_secret
+        )?;
+
+        self.sessions_repository.create(AddSessionEntity {
+            family_id: Uuid::new_v4(),
+            role: role.to_string(),
+            subject_id,
+            refresh_token_hash: hash_token(&passport.refresh_token),
+            expires_at: expires_at.naive_utc(),
+            created_at: Utc::now().naive_utc(),
+        }).await?;
+
+        Ok(passport)
+    }
+
+    async fn rotate_session(
+        &self,
+        refresh_token: String,
+        role: Roles,
+        secret: String,
+        refresh_secret: String
+    ) -> AppResult<Passport> {
+        let claims = jwt_authentication::verify_token(refresh_secret.clone(), refresh_token.clone())
+            .map_err(|_| AppError::Unauthorized("Invalid refresh token".to_string()))?;
+
+        if claims.role != role {
+            return Err(AppError::Unauthorized("Invalid refresh token".to_string()));
+        }
+
+        let session = self.find_live_session(&refresh_token).await?;
+
+        // The session lifetime is fixed at login, rotation never extends it.
+        let passport = Self::sign_passport(claims.sub, role, claims.exp, secret, refresh_secret)?;
+
+        let rotation = self.sessions_repository.rotate(session.id, AddSessionEntity {
+            family_id: session.family_id,
+            role: session.role,
+            subject_id: session.subject_id,
+            refresh_token_hash: hash_token(&passport.refresh_token),
+            expires_at: session.expires_at,
+            created_at: Utc::now().naive_utc(),
+        }).await;
+
+        match rotation {
+            Ok(_) => Ok(passport),
+            // Another request rotated this token first, treat it like any other reuse.
+            Err(AppError::Conflict(_)) => self.reject_reuse(session.family_id).await,
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn end_session(
+        &self,
+        refresh_token: String,
+        role: Roles,
+        refresh_secret: String
+    ) -> AppResult<()> {
+        // A token this role did not sign, or one that has expired, has no session left to end.
+        let Ok(claims) = jwt_authentication::verify_token(refresh_secret, refresh_token.clone()) else {
+            return Ok(());
+        };
+
+        if claims.role != role {
+            return Ok(());
+        }
+
+        match self.sessions_repository.find_by_token_hash(hash_token(&refresh_token)).await {
+            Ok(session) if session.role == role.to_string() =>
+                self.sessions_repository.revoke_family(session.family_id).await,
+            Ok(_) => Ok(()),
+            Err(AppError::NotFound(_)) => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn end_all_sessions(
+        &self,
+        refresh_token: String,
+        role: Roles,
+        refresh_secret: String
+    ) -> AppResult<()> {
+        let claims = jwt_authentication::verify_token(refresh_secret, refresh_token.clone())
+            .map_err(|_| AppError::Unauthorized("Invalid refresh token".to_string()))?;
+
+        if claims.role != role {
+            return Err(AppError::Unauthorized("Invalid refresh token".to_string()));
+        }
+
+        let session = self.find_live_session(&refresh_token).await?;
+
+        self.sessions_repository.revoke_all_by_subject(session.role, session.subject_id).await
+    }
+
+    /// Looks up the session behind a refresh token, revoking its whole family
+    /// when the token has already been exchanged once.
+    async fn find_live_session(&self, refresh_token: &str) -> AppResult<SessionEntity> {
+        let session = self.sessions_repository
+            .find_by_token_hash(hash_token(refresh_token)).await
+            .map_err(|err| match err {
+                AppError::NotFound(_) => AppError::Unauthorized("Invalid refresh token".to_string()),
+                err => err,
+            })?;
+
+        if session.revoked_at.is_some() {
+            return Err(AppError::Unauthorized("Session has been revoked".to_string()));
+        }
+
+        if session.rotated_at.is_some() {
+            return self.reject_reuse(session.family_id).await;
+        }
+
+        Ok(session)
+    }
+
+    async fn reject_reuse<T>(&self, family_id: Uuid) -> AppResult<T> {
+        self.sessions_repository.revoke_family(family_id).await?;
+
+        Err(AppError::Unauthorized("Refresh token reuse detected, session revoked".to_string()))
+    }
 
+    fn sign_passport(
+        sub: String,
+        role: Roles,
+        refresh_exp: usize,
+        secret: String,
+        refresh_secret: String
+    ) -> AppResult<Passport> {
         let access_token_claims = Claims {
-            sub: claims.sub.clone(),
-            role: Roles::GuildCommander,
+            sub: sub.clone(),
+            role: role.clone(),
             exp: (Utc::now() + Duration::days(1)).timestamp() as usize,
             iat: Utc::now().timestamp() as usize,
+            jti: None,
         };
 
         let refresh_token_claims = Claims {
-            sub: claims.sub.clone(),
-            role: Roles::GuildCommander,
-            exp: claims.exp,
+            sub,
+            role,
+            exp: refresh_exp,
             iat: Utc::now().timestamp() as usize,
+            jti: Some(Uuid::new_v4().to_string()),
         };
 
-        let access_token = generate_token(secret_env.secret, &access_token_claims)?;
-        let refresh_token = generate_token(secret_env.refresh_secret, &refresh_token_claims)?;
+        let access_token = generate_token(secret, &access_token_claims)?;
+        let refresh_token = generate_token(refresh_secret, &refresh_token_claims)?;
 
         Ok(Passport {
             access_token,
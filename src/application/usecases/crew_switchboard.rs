@@ -1,78 +1,230 @@
 use std::sync::Arc;
 
-use anyhow::Result;
-
-use crate::domain::{
-    repositories::{
-        crew_switchboard::CrewSwitchboardRepository,
-        quest_viewing::QuestViewingRepository,
-    },
-    value_objects::{
-        quest_adventurer_junction::{ MAX_ADVENTURERS_PER_QUEST, QuestAdventurerJunction },
-        quest_statuses::QuestStatus,
+use crate::{
+    application::{ caches::board_cache::BoardCache, policies::quest_ownership::ensure_quest_owner },
+    config::config_loader,
+    domain::{
+        entities::{
+            quest_applications::AddQuestApplicationEntity,
+            quest_invitations::AddQuestInvitationEntity,
+        },
+        errors::AppResult,
+        repositories::{
+            adventurers::AdventurersRepository,
+            crew_switchboard::CrewSwitchboardRepository,
+            quest_viewing::QuestViewingRepository,
+        },
+        value_objects::{
+            ids::{ AdventurerId, GuildCommanderId, QuestApplicationId, QuestId, QuestInvitationId },
+            quest_adventurer_junction::QuestAdventurerJunction,
+            quest_applications::{
+                ApplicationFilter,
+                ApplicationStatus,
+                ApplyQuestModel,
+                QuestApplicationModel,
+                normalize_message,
+            },
+            quest_invitations::{ InvitationStatus, InviteAdventurerModel, QuestInvitationModel },
+            quest_waitlist::{ JoinOptions, JoinOutcome },
+        },
     },
 };
 
-pub struct CrewSwitchboardUseCase<T1, T2>
-    where T1: QuestViewingRepository + Send + Sync, T2: CrewSwitchboardRepository + Send + Sync {
-    quest_viewing_repository: Arc<T1>,
-    crew_switchboard_repository: Arc<T2>,
+pub struct CrewSwitchboardUseCase<T1, T2, T3>
+    where
+        T1: CrewSwitchboardRepository + Send + Sync,
+        T2: QuestViewingRepository + Send + Sync,
+        T3: AdventurersRepository + Send + Sync
+{
+    crew_switchboard_repository: Arc<T1>,
+    quest_viewing_repository: Arc<T2>,
+    adventurers_repository: Arc<T3>,
+    board_cache: Arc<BoardCache>,
 }
 
-impl<T1, T2> CrewSwitchboardUseCase<T1, T2>
-    where T1: QuestViewingRepository + Send + Sync, T2: CrewSwitchboardRepository + Send + Sync
+impl<T1, T2, T3> CrewSwitchboardUseCase<T1, T2, T3>
+    where
+        T1: CrewSwitchboardRepository + Send + Sync,
+        T2: QuestViewingRepository + Send + Sync,
+        T3: AdventurersRepository + Send + Sync
 {
-    pub fn new(quest_viewing_repository: Arc<T1>, crew_switchboard_repository: Arc<T2>) -> Self {
+    pub fn new(
+        crew_switchboard_repository: Arc<T1>,
+        quest_viewing_repository: Arc<T2>,
+        adventurers_repository: Arc<T3>,
+        board_cache: Arc<BoardCache>
+    ) -> Self {
         Self {
-            quest_viewing_repository,
             crew_switchboard_repository,
+            quest_viewing_repository,
+            adventurers_repository,
+            board_cache,
         }
     }
 
-    pub async fn join(&self, quest_id: i32, adventurer_id: i32) -> Result<()> {
-        let quest = self.quest_viewing_repository.view_details(quest_id).await?;
-
-        let adventurers_count =
-            self.quest_viewing_repository.adventurers_counting_by_quest_id(quest_id).await?;
+    pub async fn join(
+        &self,
+        quest_id: QuestId,
+        adventurer_id: AdventurerId,
+        options: &JoinOptions
+    ) -> AppResult<JoinOutcome> {
+        let outcome = self.crew_switchboard_repository.join(QuestAdventurerJunction {
+            adventurer_id,
+            quest_id,
+        }, options.waitlist).await?;
 
-        let quest_status_condition =
-            quest.status == QuestStatus::Open.to_string() ||
-            quest.status == QuestStatus::Failed.to_string();
+        if outcome == JoinOutcome::Joined {
+            self.board_cache.invalidate();
+        }
 
-        let adventurers_count_condition = adventurers_count < MAX_ADVENTURERS_PER_QUEST;
+        Ok(outcome)
+    }
 
-        if !adventurers_count_condition {
-            return Err(anyhow::anyhow!("Quest is full"));
-        }
+    pub async fn leave(&self, quest_id: QuestId, adventurer_id: AdventurerId) -> AppResult<()> {
+        self.crew_switchboard_repository.leave(QuestAdventurerJunction {
+            adventurer_id,
+            quest_id,
+        }).await?;
+        self.board_cache.invalidate();
 
-        if !quest_status_condition {
-            return Err(anyhow::anyhow!("Quest is not joinable"));
-        }
+        Ok(())
+    }
 
-        self.crew_switchboard_repository.join(QuestAdventurerJunction {
+    pub async fn leave_waitlist(&self, quest_id: QuestId, adventurer_id: AdventurerId) -> AppResult<()> {
+        self.crew_switchboard_repository.leave_waitlist(QuestAdventurerJunction {
             adventurer_id,
             quest_id,
+        }).await
+    }
+
+    pub async fn apply(
+        &self,
+        quest_id: QuestId,
+        adventurer_id: AdventurerId,
+        apply_quest_model: ApplyQuestModel
+    ) -> AppResult<QuestApplicationId> {
+        let now = chrono::Utc::now().naive_utc();
+
+        let result = self.crew_switchboard_repository.apply(AddQuestApplicationEntity {
+            quest_id,
+            adventurer_id,
+            message: normalize_message(apply_quest_model.message.as_deref())?,
+            status: ApplicationStatus::Pending,
+            created_at: now,
+            updated_at: now,
         }).await?;
 
+        Ok(result)
+    }
+
+    pub async fn withdraw(&self, quest_id: QuestId, adventurer_id: AdventurerId) -> AppResult<()> {
+        self.crew_switchboard_repository.withdraw(quest_id, adventurer_id).await
+    }
+
+    pub async fn applications(
+        &self,
+        quest_id: QuestId,
+        guild_commander_id: GuildCommanderId,
+        filter: &ApplicationFilter
+    ) -> AppResult<Vec<QuestApplicationModel>> {
+        let quest = self.quest_viewing_repository.view_details(quest_id).await?;
+
+        ensure_quest_owner(&quest, guild_commander_id)?;
+
+        let results = self.crew_switchboard_repository.applications(quest_id, filter.status).await?;
+
+        Ok(results.iter().map(|application| application.to_model()).collect())
+    }
+
+    pub async fn approve(
+        &self,
+        application_id: QuestApplicationId,
+        guild_commander_id: GuildCommanderId
+    ) -> AppResult<()> {
+        self.ensure_application_owner(application_id, guild_commander_id).await?;
+
+        self.crew_switchboard_repository.approve(application_id).await?;
+        self.board_cache.invalidate();
+
         Ok(())
     }
 
-    pub async fn leave(&self, quest_id: i32, adventurer_id: i32) -> Result<()> {
+    pub async fn reject(
+        &self,
+        application_id: QuestApplicationId,
+        guild_commander_id: GuildCommanderId
+    ) -> AppResult<()> {
+        self.ensure_application_owner(application_id, guild_commander_id).await?;
+
+        self.crew_switchboard_repository.reject(application_id).await
+    }
+
+    /// Invitations skip the join policy, but accepting one still goes through every other check.
+    pub async fn invite(
+        &self,
+        quest_id: QuestId,
+        guild_commander_id: GuildCommanderId,
+        invite_adventurer_model: InviteAdventurerModel
+    ) -> AppResult<QuestInvitationId> {
         let quest = self.quest_viewing_repository.view_details(quest_id).await?;
 
-        let leaving_condition =
-            quest.status == QuestStatus::Open.to_string() ||
-            quest.status == QuestStatus::Failed.to_string();
+        ensure_quest_owner(&quest, guild_commander_id)?;
 
-        if !leaving_condition {
-            return Err(anyhow::anyhow!("Quest is not leaveable"));
-        }
+        let adventurer = self.adventurers_repository
+            .find_by_username(invite_adventurer_model.username.trim().to_string()).await?;
 
-        self.crew_switchboard_repository.leave(QuestAdventurerJunction {
-            adventurer_id,
+        let ttl = chrono::Duration::hours(config_loader::load()?.invitations.ttl_hours);
+        let now = chrono::Utc::now().naive_utc();
+
+        let result = self.crew_switchboard_repository.invite(AddQuestInvitationEntity {
             quest_id,
+            adventurer_id: adventurer.id,
+            guild_commander_id,
+            status: InvitationStatus::Pending,
+            expires_at: now + ttl,
+            created_at: now,
+            updated_at: now,
         }).await?;
 
+        Ok(result)
+    }
+
+    pub async fn invitations(&self, adventurer_id: AdventurerId) -> AppResult<Vec<QuestInvitationModel>> {
+        let results = self.crew_switchboard_repository
+            .invitations(adventurer_id, chrono::Utc::now().naive_utc()).await?;
+
+        Ok(results.iter().map(|invitation| invitation.to_model()).collect())
+    }
+
+    pub async fn accept_invitation(
+        &self,
+        invitation_id: QuestInvitationId,
+        adventurer_id: AdventurerId
+    ) -> AppResult<()> {
+        self.crew_switchboard_repository
+            .accept_invitation(invitation_id, adventurer_id, chrono::Utc::now().naive_utc()).await?;
+        self.board_cache.invalidate();
+
         Ok(())
     }
+
+    pub async fn decline_invitation(
+        &self,
+        invitation_id: QuestInvitationId,
+        adventurer_id: AdventurerId
+    ) -> AppResult<()> {
+        self.crew_switchboard_repository
+            .decline_invitation(invitation_id, adventurer_id, chrono::Utc::now().naive_utc()).await
+    }
+
+    async fn ensure_application_owner(
+        &self,
+        application_id: QuestApplicationId,
+        guild_commander_id: GuildCommanderId
+    ) -> AppResult<()> {
+        let application = self.crew_switchboard_repository.find_application(application_id).await?;
+        let quest = self.quest_viewing_repository.view_details(application.quest_id).await?;
+
+        ensure_quest_owner(&quest, guild_commander_id)
+    }
 }
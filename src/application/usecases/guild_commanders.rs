@@ -1,10 +1,9 @@
 use std::sync::Arc;
 
-use anyhow::Result;
-
 use crate::{domain::{
+    errors::AppResult,
     repositories::guild_commanders::GuildCommanderRepository,
-    value_objects::guild_commander_model::RegisterGuildCommanderModel,
+    value_objects::{ guild_commander_model::RegisterGuildCommanderModel, ids::GuildCommanderId },
 }, infrastructure::argon2_hashing};
 
 pub struct GuildCommandersUseCase<T> where T: GuildCommanderRepository + Send + Sync {
@@ -21,7 +20,7 @@ impl<T> GuildCommandersUseCase<T> where T: GuildCommanderRepository + Send + Syn
     pub async fn register(
         &self,
         mut register_guild_commander_model: RegisterGuildCommanderModel
-    ) -> Result<i32> {
+    ) -> AppResult<GuildCommanderId> {
         let hashed_password = argon2_hashing::hash(register_guild_commander_model.password.clone())?;
         register_guild_commander_model.password = hashed_password;
 
@@ -1,15 +1,21 @@
 use std::sync::Arc;
 
-use anyhow::Result;
-
-use crate::domain::{
-    repositories::{
-        journey_ledger::JourneyLedgerRepository,
-        quest_viewing::QuestViewingRepository,
-    },
-    value_objects::{
-        quest_adventurer_junction::MAX_ADVENTURERS_PER_QUEST,
-        quest_statuses::QuestStatus,
+use crate::{
+    application::{ caches::board_cache::BoardCache, policies::quest_ownership::ensure_quest_owner },
+    config::config_loader,
+    domain::{
+        entities::quests::QuestEntity,
+        errors::AppResult,
+        repositories::{
+            journey_ledger::JourneyLedgerRepository,
+            quest_viewing::QuestViewingRepository,
+        },
+        value_objects::{
+            ids::{ GuildCommanderId, QuestId },
+            quest_status_event_model::QuestStatusEventModel,
+            quest_statuses::{ QuestStatus, TransitionContext, TransitionEffect },
+            quest_transition_model::QuestTransitionsModel,
+        },
     },
 };
 
@@ -17,64 +23,112 @@ pub struct JourneyLedgerUseCase<T1, T2>
     where T1: JourneyLedgerRepository + Send + Sync, T2: QuestViewingRepository + Send + Sync {
     journey_ledger_repository: Arc<T1>,
     quest_viewing_repository: Arc<T2>,
+    board_cache: Arc<BoardCache>,
 }
 
 impl<T1, T2> JourneyLedgerUseCase<T1, T2>
     where T1: JourneyLedgerRepository + Send + Sync, T2: QuestViewingRepository + Send + Sync
 {
-    pub fn new(journey_ledger_repository: Arc<T1>, quest_viewing_repository: Arc<T2>) -> Self {
+    pub fn new(
+        journey_ledger_repository: Arc<T1>,
+        quest_viewing_repository: Arc<T2>,
+        board_cache: Arc<BoardCache>
+    ) -> Self {
         Self {
             journey_ledger_repository,
             quest_viewing_repository,
+            board_cache,
         }
     }
 
-    pub async fn in_journey(&self, quest_id: i32, guild_commander_id: i32) -> Result<i32> {
-        let quest = self.quest_viewing_repository.view_details(quest_id).await?;
+    pub async fn allowed_transitions(
+        &self,
+        quest_id: QuestId,
+        guild_commander_id: GuildCommanderId
+    ) -> AppResult<QuestTransitionsModel> {
+        let (quest, context) = self.load_owned_quest(quest_id, guild_commander_id).await?;
+
+        Ok(QuestTransitionsModel {
+            quest_id,
+            status: quest.status,
+            allowed_next: quest.status.allowed_next(&context),
+        })
+    }
 
-        let adventurers_count =
-            self.quest_viewing_repository.adventurers_counting_by_quest_id(quest_id).await?;
+    pub async fn transition(
+        &self,
+        quest_id: QuestId,
+        guild_commander_id: GuildCommanderId,
+        to: QuestStatus,
+        reason: Option<String>
+    ) -> AppResult<QuestTransitionsModel> {
+        let (quest, mut context) = self.load_owned_quest(quest_id, guild_commander_id).await?;
 
-        let conditions_to_update =
-            quest.status == QuestStatus::Open.to_string() ||
-            (quest.status == QuestStatus::Failed.to_string() &&
-                adventurers_count > 0 &&
-                adventurers_count <= MAX_ADVENTURERS_PER_QUEST);
+        let transition = quest.status.transition_to(to, &context)?;
 
-        if !conditions_to_update {
-            return Err(anyhow::anyhow!("Cannot set quest to In Journey status."));
+        let progression = config_loader::load()?.progression.rules();
+
+        self.journey_ledger_repository
+            .transition(quest_id, guild_commander_id, transition, reason, progression).await?;
+        self.board_cache.invalidate();
+
+        if transition.effects.contains(&TransitionEffect::ReleaseCrew) {
+            context.crew_count = 0;
         }
 
-        let result = self.journey_ledger_repository.in_journey(quest_id, guild_commander_id).await?;
+        Ok(QuestTransitionsModel {
+            quest_id,
+            status: to,
+            allowed_next: to.allowed_next(&context),
+        })
+    }
 
-        Ok(result)
+    pub async fn in_journey(&self, quest_id: QuestId, guild_commander_id: GuildCommanderId) -> AppResult<QuestId> {
+        let result = self.transition(quest_id, guild_commander_id, QuestStatus::InJourney, None).await?;
+
+        Ok(result.quest_id)
     }
 
-    pub async fn to_completed(&self, quest_id: i32, guild_commander_id: i32) -> Result<i32> {
-        let quest = self.quest_viewing_repository.view_details(quest_id).await?;
+    pub async fn to_completed(&self, quest_id: QuestId, guild_commander_id: GuildCommanderId) -> AppResult<QuestId> {
+        let result = self.transition(quest_id, guild_commander_id, QuestStatus::Completed, None).await?;
 
-        let condition_to_update = quest.status == QuestStatus::InJourney.to_string();
+        Ok(result.quest_id)
+    }
 
-        if !condition_to_update {
-            return Err(anyhow::anyhow!("Cannot set quest to Completed status."));
-        }
+    pub async fn to_failed(&self, quest_id: QuestId, guild_commander_id: GuildCommanderId) -> AppResult<QuestId> {
+        let result = self.transition(quest_id, guild_commander_id, QuestStatus::Failed, None).await?;
 
-        let result = self.journey_ledger_repository.to_complete(quest_id, guild_commander_id).await?;
+        Ok(result.quest_id)
+    }
+
+    pub async fn history(
+        &self,
+        quest_id: QuestId,
+        guild_commander_id: GuildCommanderId
+    ) -> AppResult<Vec<QuestStatusEventModel>> {
+        let quest = self.quest_viewing_repository.view_details(quest_id).await?;
 
-        Ok(result)
+        ensure_quest_owner(&quest, guild_commander_id)?;
+
+        let events = self.journey_ledger_repository.history(quest_id).await?;
+
+        Ok(events.iter().map(|event| event.to_model()).collect())
     }
 
-    pub async fn to_failed(&self, quest_id: i32, guild_commander_id: i32) -> Result<i32> {
+    async fn load_owned_quest(
+        &self,
+        quest_id: QuestId,
+        guild_commander_id: GuildCommanderId
+    ) -> AppResult<(QuestEntity, TransitionContext)> {
         let quest = self.quest_viewing_repository.view_details(quest_id).await?;
 
-        let condition_to_update = quest.status == QuestStatus::InJourney.to_string();
+        ensure_quest_owner(&quest, guild_commander_id)?;
 
-        if !condition_to_update {
-            return Err(anyhow::anyhow!("Cannot set quest to Failed status."));
-        }
+        let crew_count =
+            self.quest_viewing_repository.adventurers_counting_by_quest_id(quest_id).await?;
 
-        let result = self.journey_ledger_repository.to_failed(quest_id, guild_commander_id).await?;
+        let context = TransitionContext::for_quest(&quest, crew_count);
 
-        Ok(result)
+        Ok((quest, context))
     }
 }
@@ -2,6 +2,8 @@ pub mod adventurers;
 pub mod crew_switchboard;
 pub mod guild_commanders;
 pub mod journey_ledger;
+pub mod notifications;
+pub mod quest_deadlines;
 pub mod quest_ops;
 pub mod quest_viewing;
 pub mod authentication;
\ No newline at end of file
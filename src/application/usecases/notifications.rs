@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    errors::AppResult,
+    repositories::notifications::NotificationsRepository,
+    value_objects::{
+        ids::{ AdventurerId, NotificationId },
+        notification_model::{ MAX_NOTIFICATIONS, NotificationFilter, NotificationModel },
+    },
+};
+
+pub struct NotificationsUseCase<T> where T: NotificationsRepository + Send + Sync {
+    notifications_repository: Arc<T>,
+}
+
+impl<T> NotificationsUseCase<T> where T: NotificationsRepository + Send + Sync {
+    pub fn new(notifications_repository: Arc<T>) -> Self {
+        Self {
+            notifications_repository,
+        }
+    }
+
+    pub async fn list(
+        &self,
+        adventurer_id: AdventurerId,
+        filter: &NotificationFilter
+    ) -> AppResult<Vec<NotificationModel>> {
+        let results = self.notifications_repository
+            .list(adventurer_id, filter.unread_only, MAX_NOTIFICATIONS).await?;
+
+        Ok(results.iter().map(|notification| notification.to_model()).collect())
+    }
+
+    pub async fn mark_read(&self, notification_id: NotificationId, adventurer_id: AdventurerId) -> AppResult<()> {
+        self.notifications_repository.mark_read(notification_id, adventurer_id).await
+    }
+}
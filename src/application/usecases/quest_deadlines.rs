@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+
+use crate::{
+    application::caches::board_cache::BoardCache,
+    config::config_loader,
+    domain::{
+        errors::AppResult,
+        repositories::quest_deadlines::QuestDeadlinesRepository,
+        value_objects::{ ids::QuestId, quest_statuses::{ QuestStatus, QuestTransition } },
+    },
+};
+
+/// Edges taken once a quest's `due_at` has passed, with the reason recorded in its history.
+const OVERDUE_TRANSITIONS: [(QuestStatus, QuestStatus, &str); 2] = [
+    (QuestStatus::InJourney, QuestStatus::Failed, "Due date passed before the journey was completed"),
+    (QuestStatus::Open, QuestStatus::Cancelled, "Due date passed before the journey started"),
+];
+
+/// Edge taken once an Open quest's `starts_at` has passed while its crew is below `min_crew`.
+const UNSTARTED_TRANSITION: (QuestStatus, QuestStatus, &str) =
+    (QuestStatus::Open, QuestStatus::Cancelled, "Start time passed without enough crew");
+
+pub struct QuestDeadlinesUseCase<T> where T: QuestDeadlinesRepository + Send + Sync {
+    quest_deadlines_repository: Arc<T>,
+    board_cache: Arc<BoardCache>,
+}
+
+impl<T> QuestDeadlinesUseCase<T> where T: QuestDeadlinesRepository + Send + Sync {
+    pub fn new(quest_deadlines_repository: Arc<T>, board_cache: Arc<BoardCache>) -> Self {
+        Self {
+            quest_deadlines_repository,
+            board_cache,
+        }
+    }
+
+    /// Fails overdue InJourney quests, cancels overdue Open ones and those whose
+    /// start time passed without enough crew, returning the quests that were moved.
+    pub async fn sweep_overdue(&self) -> AppResult<Vec<QuestId>> {
+        let now = chrono::Utc::now().naive_utc();
+        let progression = config_loader::load()?.progression.rules();
+
+        let mut swept = Vec::new();
+
+        for (from, to, reason) in OVERDUE_TRANSITIONS {
+            // `None` means another instance holds the sweep lock, it will do the work.
+            if let Some(quest_ids) = self.quest_deadlines_repository
+                .sweep_overdue(find_transition(from, to)?, now, reason.to_string(), progression).await?
+            {
+                swept.extend(quest_ids);
+            }
+        }
+
+        let (from, to, reason) = UNSTARTED_TRANSITION;
+
+        if let Some(quest_ids) = self.quest_deadlines_repository
+            .sweep_unstarted(find_transition(from, to)?, now, reason.to_string(), progression).await?
+        {
+            swept.extend(quest_ids);
+        }
+
+        if !swept.is_empty() {
+            self.board_cache.invalidate();
+        }
+
+        Ok(swept)
+    }
+}
+
+fn find_transition(from: QuestStatus, to: QuestStatus) -> AppResult<QuestTransition> {
+    let transition = QuestTransition::find(from, to)
+        .ok_or_else(|| anyhow!("Missing quest transition from {} to {}", from, to))?;
+
+    Ok(transition)
+}
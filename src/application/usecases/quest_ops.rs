@@ -1,11 +1,25 @@
-use std::{ sync::Arc };
-
-use anyhow::{ Ok, Result };
+use std::{ collections::HashMap, sync::Arc };
 
 use crate::{
+    application::{ caches::board_cache::BoardCache, policies::quest_ownership::ensure_quest_owner },
+    config::config_loader,
     domain::{
+        errors::{ AppError, AppResult },
         repositories::{ quest_ops::QuestOpsRepository, quest_viewing::QuestViewingRepository },
-        value_objects::quest_model::{ AddQuestModel, EditQuestModel },
+        value_objects::{
+            ids::{ GuildCommanderId, QuestId },
+            quest_adventurer_junction::validate_crew_size,
+            quest_model::{
+                AddQuestModel,
+                EditQuestModel,
+                QuestModel,
+                TrashedQuestModel,
+                validate_min_level,
+                validate_rewards,
+                validate_schedule,
+            },
+            tag_model::normalize_tags,
+        },
     },
 };
 
@@ -13,55 +27,164 @@ pub struct QuestOpsUseCase<T1, T2>
     where T1: QuestOpsRepository + Send + Sync, T2: QuestViewingRepository + Send + Sync {
     quest_ops_repository: Arc<T1>,
     quest_viewing_repository: Arc<T2>,
+    board_cache: Arc<BoardCache>,
 }
 
 impl<T1, T2> QuestOpsUseCase<T1, T2>
     where T1: QuestOpsRepository + Send + Sync, T2: QuestViewingRepository + Send + Sync
 {
-    pub fn new(quest_ops_repository: Arc<T1>, quest_viewing_repository: Arc<T2>) -> Self {
+    pub fn new(
+        quest_ops_repository: Arc<T1>,
+        quest_viewing_repository: Arc<T2>,
+        board_cache: Arc<BoardCache>
+    ) -> Self {
         Self {
             quest_ops_repository,
             quest_viewing_repository,
+            board_cache,
         }
     }
 
-    pub async fn add(&self, commander_id: i32, add_quest_model: AddQuestModel) -> Result<i32> {
+    pub async fn add(&self, commander_id: GuildCommanderId, add_quest_model: AddQuestModel) -> AppResult<QuestId> {
         let add_quest_entity = add_quest_model.to_entity(commander_id);
 
-        let result = self.quest_ops_repository.add(add_quest_entity).await?;
+        validate_crew_size(add_quest_entity.min_crew, add_quest_entity.max_crew)?;
+        validate_schedule(
+            add_quest_entity.starts_at,
+            add_quest_entity.due_at,
+            add_quest_entity.created_at
+        )?;
+        validate_rewards(add_quest_entity.reward_xp, add_quest_entity.reward_gold)?;
+        validate_min_level(add_quest_entity.min_level)?;
+        let tags = normalize_tags(&add_quest_model.tags)?;
+
+        let result = self.quest_ops_repository.add(add_quest_entity, tags).await?;
+        self.board_cache.invalidate();
 
         Ok(result)
     }
 
     pub async fn edit(
         &self,
-        quest_id: i32,
-        commander_id: i32,
+        quest_id: QuestId,
+        commander_id: GuildCommanderId,
+        expected_version: Option<i32>,
         edit_quest_model: EditQuestModel
-    ) -> Result<i32> {
+    ) -> AppResult<QuestId> {
+        let quest = self.quest_viewing_repository.view_details(quest_id).await?;
+
+        ensure_quest_owner(&quest, commander_id)?;
+
+        if let Some(expected_version) = expected_version {
+            if expected_version != quest.version {
+                return Err(
+                    AppError::PreconditionFailed(
+                        format!("Quest {} is at version {}, not {}", quest_id, quest.version, expected_version)
+                    )
+                );
+            }
+        }
+
+        if !quest.status.is_editable() {
+            return Err(AppError::Conflict(format!("Cannot edit quest in {} status.", quest.status)));
+        }
+
         let adventurers_count =
             self.quest_viewing_repository.adventurers_counting_by_quest_id(quest_id).await?;
 
         if adventurers_count > 0 {
-            return Err(anyhow::anyhow!("Cannot edit quest with adventurers assigned."));
+            return Err(AppError::Conflict("Cannot edit quest with adventurers assigned.".to_string()));
         }
 
-        let edit_quest_entity = edit_quest_model.to_entity(commander_id);
+        let edit_quest_entity = edit_quest_model.to_entity();
+
+        validate_crew_size(
+            edit_quest_entity.min_crew.unwrap_or(quest.min_crew),
+            edit_quest_entity.max_crew.unwrap_or(quest.max_crew)
+        )?;
+        validate_rewards(
+            edit_quest_entity.reward_xp.unwrap_or(quest.reward_xp),
+            edit_quest_entity.reward_gold.unwrap_or(quest.reward_gold)
+        )?;
+        validate_min_level(edit_quest_entity.min_level.unwrap_or(quest.min_level))?;
+        let tags = edit_quest_model.tags.as_deref().map(normalize_tags).transpose()?;
 
-        let result = self.quest_ops_repository.edit(quest_id, edit_quest_entity).await?;
+        let result = self.quest_ops_repository.edit(quest_id, edit_quest_entity, tags, expected_version).await?;
+        self.board_cache.invalidate();
 
         Ok(result)
     }
 
-    pub async fn remove(&self, quest_id: i32, commander_id: i32) -> Result<()> {
+    pub async fn remove(&self, quest_id: QuestId, commander_id: GuildCommanderId) -> AppResult<()> {
+        let quest = self.quest_viewing_repository.view_details(quest_id).await?;
+
+        ensure_quest_owner(&quest, commander_id)?;
+
+        if !quest.status.is_editable() {
+            return Err(AppError::Conflict(format!("Cannot remove quest in {} status.", quest.status)));
+        }
+
         let adventurers_count =
             self.quest_viewing_repository.adventurers_counting_by_quest_id(quest_id).await?;
 
         if adventurers_count > 0 {
-            return Err(anyhow::anyhow!("Cannot edit quest with adventurers assigned."));
+            return Err(AppError::Conflict("Cannot remove quest with adventurers assigned.".to_string()));
         }
         self.quest_ops_repository.remove(quest_id, commander_id).await?;
+        self.board_cache.invalidate();
 
         Ok(())
     }
+
+    /// The quest as `quest-viewing` serves it, sent back when an edit hits a stale version.
+    pub async fn current(&self, quest_id: QuestId) -> AppResult<QuestModel> {
+        let quest = self.quest_viewing_repository.view_details_with_crew_count(quest_id).await?;
+        let tags = self.quest_viewing_repository
+            .tags_by_quest_ids(vec![quest_id]).await?
+            .into_iter()
+            .map(|tag| tag.name)
+            .collect();
+
+        Ok(quest.to_model(tags))
+    }
+
+    pub async fn trash(&self, commander_id: GuildCommanderId) -> AppResult<Vec<TrashedQuestModel>> {
+        let retention = chrono::Duration::days(config_loader::load()?.trash.retention_days);
+
+        let results = self.quest_ops_repository.trash(commander_id).await?;
+
+        let mut tags: HashMap<QuestId, Vec<String>> = HashMap::new();
+
+        if !results.is_empty() {
+            let quest_ids = results.iter().map(|trashed| trashed.quest.id).collect();
+
+            for tag in self.quest_viewing_repository.tags_by_quest_ids(quest_ids).await? {
+                tags.entry(tag.quest_id).or_default().push(tag.name);
+            }
+        }
+
+        Ok(results
+            .iter()
+            .map(|trashed| trashed.to_model(tags.remove(&trashed.quest.id).unwrap_or_default(), retention))
+            .collect())
+    }
+
+    pub async fn restore(&self, quest_id: QuestId, commander_id: GuildCommanderId) -> AppResult<QuestId> {
+        let trashed = self.quest_ops_repository.find_trashed(quest_id).await?;
+
+        ensure_quest_owner(&trashed.quest, commander_id)?;
+
+        let result = self.quest_ops_repository
+            .restore(quest_id, commander_id, chrono::Utc::now().naive_utc()).await?;
+        self.board_cache.invalidate();
+
+        Ok(result)
+    }
+
+    /// Hard deletes quests that have been in the trash longer than the retention period.
+    pub async fn purge_trash(&self) -> AppResult<Vec<QuestId>> {
+        let retention = chrono::Duration::days(config_loader::load()?.trash.retention_days);
+
+        self.quest_ops_repository.purge_trashed(chrono::Utc::now().naive_utc() - retention).await
+    }
 }
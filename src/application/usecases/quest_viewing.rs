@@ -1,47 +1,228 @@
-use std::sync::Arc;
+use std::{ collections::HashMap, sync::Arc, time::Duration };
 
-use anyhow::Result;
-
-use crate::domain::{
-    repositories::quest_viewing::QuestViewingRepository,
-    value_objects::{ board_checking_filter::BoardCheckingFilter, quest_model::QuestModel },
+use crate::{
+    application::caches::board_cache::BoardCache,
+    domain::{
+        errors::{ AppError, AppResult },
+        repositories::quest_viewing::QuestViewingRepository,
+        value_objects::{
+            board_checking_filter::{ BoardCheckingFilter, BoardSort, DEFAULT_BOARD_LIMIT, MAX_BOARD_LIMIT },
+            board_cursor::BoardCursor,
+            board_page_model::BoardPageModel,
+            fuzzy_search_model::{
+                FUZZY_MATCH_THRESHOLD,
+                FuzzyQuestHitModel,
+                FuzzySearchFilter,
+                FuzzySearchModel,
+                MAX_SUGGESTION_WORDS,
+                SUGGESTION_THRESHOLD,
+            },
+            ids::{ AdventurerId, QuestId },
+            quest_model::QuestModel,
+            tag_model::TagUsageModel,
+        },
+    },
 };
 
 pub struct QuestViewingUseCase<T> where T: QuestViewingRepository + Send + Sync {
     quest_viewing_repository: Arc<T>,
+    board_cache: Arc<BoardCache>,
 }
 
 impl<T> QuestViewingUseCase<T> where T: QuestViewingRepository + Send + Sync {
-    pub fn new(quest_viewing_repository: Arc<T>) -> Self {
+    pub fn new(quest_viewing_repository: Arc<T>, board_cache: Arc<BoardCache>) -> Self {
         Self {
             quest_viewing_repository,
+            board_cache,
         }
     }
 
-    pub async fn view_details(&self, quest_id: i32) -> Result<QuestModel> {
-        let result = self.quest_viewing_repository.view_details(quest_id).await?;
+    pub async fn view_details(&self, quest_id: QuestId) -> AppResult<QuestModel> {
+        let result = self.quest_viewing_repository.view_details_with_crew_count(quest_id).await?;
+        let mut tags = self.tags_by_quest_ids(vec![quest_id]).await?;
+
+        Ok(result.to_model(tags.remove(&quest_id).unwrap_or_default()))
+    }
+
+    /// Served from the board cache when the same page was loaded within its ttl.
+    pub async fn board_checking(
+        &self,
+        filter: &BoardCheckingFilter,
+        adventurer_id: Option<AdventurerId>
+    ) -> AppResult<Arc<BoardPageModel>> {
+        let eligible_for = match (filter.eligible_for_me, adventurer_id) {
+            (false, _) => None,
+            (true, Some(adventurer_id)) => Some(adventurer_id),
+            (true, None) =>
+                return Err(AppError::Unauthorized("eligible_for_me requires an adventurer access token".to_string())),
+        };
+
+        let key = format!(
+            "{}|{}",
+            serde_json::to_string(filter).map_err(anyhow::Error::from)?,
+            eligible_for.map(|adventurer_id| adventurer_id.to_string()).unwrap_or_default()
+        );
+
+        if let Some(page) = self.board_cache.get(&key) {
+            return Ok(page);
+        }
+
+        let generation = self.board_cache.generation();
+        let page = Arc::new(self.load_board(filter, eligible_for).await?);
+
+        self.board_cache.insert(key, generation, Arc::clone(&page));
+
+        Ok(page)
+    }
+
+    /// How long a board page may be served without asking the database again.
+    pub fn board_ttl(&self) -> Duration {
+        self.board_cache.ttl()
+    }
+
+    pub async fn fuzzy_search(&self, filter: &FuzzySearchFilter) -> AppResult<FuzzySearchModel> {
+        let q = filter.q.trim().to_lowercase();
+
+        if q.is_empty() {
+            return Err(AppError::Validation("q must not be empty".to_string()));
+        }
 
-        let adventurers_count =
-            self.quest_viewing_repository.adventurers_counting_by_quest_id(quest_id).await?;
+        let limit = filter.limit.unwrap_or(DEFAULT_BOARD_LIMIT);
 
-        let quest_model = result.to_model(adventurers_count);
+        if !(1..=MAX_BOARD_LIMIT).contains(&limit) {
+            return Err(AppError::Validation(format!("limit must be between 1 and {}", MAX_BOARD_LIMIT)));
+        }
+
+        let results = self.quest_viewing_repository.fuzzy_search(q.clone(), FUZZY_MATCH_THRESHOLD, limit).await?;
+
+        if results.is_empty() {
+            return Ok(FuzzySearchModel {
+                items: Vec::new(),
+                did_you_mean: self.did_you_mean(&q).await?,
+            });
+        }
 
-        Ok(quest_model)
+        let mut tags = self.tags_by_quest_ids(results.iter().map(|hit| hit.quest.quest.id).collect()).await?;
+
+        Ok(FuzzySearchModel {
+            items: results
+                .iter()
+                .map(|hit| FuzzyQuestHitModel {
+                    quest: hit.quest.to_model(tags.remove(&hit.quest.quest.id).unwrap_or_default()),
+                    similarity: hit.similarity,
+                })
+                .collect(),
+            did_you_mean: None,
+        })
+    }
+
+    pub async fn tags(&self) -> AppResult<Vec<TagUsageModel>> {
+        let results = self.quest_viewing_repository.tag_usage().await?;
+
+        Ok(results.iter().map(|tag| tag.to_model()).collect())
     }
 
-    pub async fn board_checking(&self, filter: &BoardCheckingFilter) -> Result<Vec<QuestModel>> {
-        let results = self.quest_viewing_repository.board_checking(filter).await?;
+    async fn load_board(
+        &self,
+        filter: &BoardCheckingFilter,
+        eligible_for: Option<AdventurerId>
+    ) -> AppResult<BoardPageModel> {
+        let limit = filter.limit.unwrap_or(DEFAULT_BOARD_LIMIT);
+
+        if !(1..=MAX_BOARD_LIMIT).contains(&limit) {
+            return Err(AppError::Validation(format!("limit must be between 1 and {}", MAX_BOARD_LIMIT)));
+        }
 
-        let mut quests_model: Vec<QuestModel> = Vec::new();
+        let sort = filter.sort();
 
-        for quest in results.into_iter() {
-            let adventurers_count = self.quest_viewing_repository.adventurers_counting_by_quest_id(
-                quest.id
-            ).await?;
+        if sort == BoardSort::Relevance && filter.search_query().is_none() {
+            return Err(AppError::Validation("sort=relevance requires q".to_string()));
+        }
+
+        let cursor = filter.cursor
+            .as_deref()
+            .map(|cursor| BoardCursor::decode(cursor, sort, filter.direction))
+            .transpose()?;
+
+        // One extra row tells us whether another page follows.
+        let mut results = self.quest_viewing_repository.board_checking(filter, eligible_for, cursor, limit + 1).await?;
+
+        let has_more = results.len() as i64 > limit;
+        results.truncate(limit as usize);
+
+        let quest_ids = results.iter().map(|quest| quest.quest.id).collect::<Vec<_>>();
+
+        let mut search_hits = match filter.search_query() {
+            Some(q) if !quest_ids.is_empty() =>
+                self.quest_viewing_repository
+                    .search_hits(quest_ids.clone(), q.to_string()).await?
+                    .into_iter()
+                    .map(|hit| (hit.quest_id, hit))
+                    .collect(),
+            _ => HashMap::new(),
+        };
+
+        let next_cursor = match results.last() {
+            Some(last) if has_more => {
+                let rank = search_hits.get(&last.quest.id).map(|hit| hit.rank);
+
+                Some(BoardCursor::after(sort, filter.direction, last, rank).encode()?)
+            }
+            _ => None,
+        };
+
+        let total = self.quest_viewing_repository.board_counting(filter, eligible_for).await?;
+
+        let mut tags = self.tags_by_quest_ids(quest_ids).await?;
+
+        Ok(BoardPageModel {
+            items: results
+                .iter()
+                .map(|quest| {
+                    let mut model = quest.to_model(tags.remove(&quest.quest.id).unwrap_or_default());
+                    model.search = search_hits.remove(&quest.quest.id).map(|hit| hit.to_model());
+                    model
+                })
+                .collect(),
+            next_cursor,
+            total,
+        })
+    }
+
+    /// Replaces each word of `q` with the closest word found in quest names.
+    /// `None` when the search is too long or nothing would change.
+    async fn did_you_mean(&self, q: &str) -> AppResult<Option<String>> {
+        let words = q.split_whitespace().collect::<Vec<_>>();
+
+        if words.len() > MAX_SUGGESTION_WORDS {
+            return Ok(None);
+        }
+
+        let mut suggestion = Vec::with_capacity(words.len());
+
+        for word in &words {
+            let closest = self.quest_viewing_repository
+                .closest_name_word(word.to_string(), SUGGESTION_THRESHOLD).await?;
+
+            suggestion.push(closest.unwrap_or_else(|| word.to_string()));
+        }
+
+        let suggestion = suggestion.join(" ");
+
+        Ok((suggestion != words.join(" ")).then_some(suggestion))
+    }
+
+    async fn tags_by_quest_ids(&self, quest_ids: Vec<QuestId>) -> AppResult<HashMap<QuestId, Vec<String>>> {
+        let mut tags: HashMap<QuestId, Vec<String>> = HashMap::new();
+
+        if quest_ids.is_empty() {
+            return Ok(tags);
+        }
 
-            quests_model.push(quest.to_model(adventurers_count));
+        for tag in self.quest_viewing_repository.tags_by_quest_ids(quest_ids).await? {
+            tags.entry(tag.quest_id).or_default().push(tag.name);
         }
 
-        Ok(quests_model)
+        Ok(tags)
     }
 }
@@ -1,15 +1,24 @@
 use anyhow::{ Context, Result };
 use std::{ env, str::FromStr };
 
-use crate::config::config_model::{
-    AdventurersSecret,
-    AppConfig,
-    Database,
-    GuildCommandersSecret,
-    Server,
+use crate::{
+    config::config_model::{
+        AdventurersSecret,
+        AppConfig,
+        Authentication,
+        BoardCache,
+        Database,
+        GuildCommandersSecret,
+        Invitations,
+        Progression,
+        Scheduler,
+        Server,
+        Trash,
+    },
+    domain::value_objects::progression::{ MAX_GROWTH, MIN_GROWTH },
 };
 
-use super::stage::Stage;
+use super::{ stage::Stage, token_source::TokenSource };
 
 pub fn load() -> Result<AppConfig> {
     // โหลด .env ครั้งเดียวในที่นี้ (หรือจะไปไว้ใน main ก็ได้)
@@ -25,6 +34,8 @@ pub fn load() -> Result<AppConfig> {
 
     let database = Database {
         url: env::var("DATABASE_URL").context("DATABASE_URL is not set")?,
+        max_connections: get_env_var_or("DATABASE_MAX_CONNECTIONS", 10)?,
+        connection_timeout: get_env_var_or("DATABASE_CONNECTION_TIMEOUT", 30)?,
     };
 
     let adventurers_secret = AdventurersSecret {
@@ -43,12 +54,34 @@ pub fn load() -> Result<AppConfig> {
             .context("JWT_GUILD_COMMANDER_REFRESH_SECRET is not set")?,
     };
 
+    let authentication = Authentication {
+        token_precedence: load_token_precedence()?,
+    };
+
+    let scheduler = Scheduler {
+        quest_deadlines_interval: get_env_var_or("SCHEDULER_QUEST_DEADLINES_INTERVAL", 60)?,
+        quest_trash_interval: get_env_var_or("SCHEDULER_QUEST_TRASH_INTERVAL", 3600)?,
+    };
+
+    let progression = load_progression()?;
+
+    let board_cache = BoardCache {
+        ttl: get_env_var_or("BOARD_CACHE_TTL", 5)?,
+        capacity: get_env_var_or("BOARD_CACHE_CAPACITY", 256)?,
+    };
+
     Ok(AppConfig {
         stage,
         server,
         database,
         adventurers_secret,
         guild_commanders_secret,
+        authentication,
+        scheduler,
+        progression,
+        trash: load_trash()?,
+        board_cache,
+        invitations: load_invitations()?,
     })
 }
 
@@ -60,6 +93,16 @@ fn get_env_var<T>(key: &str) -> Result<T>
     raw.parse::<T>().with_context(|| format!("{key} is invalid: {raw}"))
 }
 
+/// เหมือน get_env_var แต่คืนค่า default เมื่อไม่ได้ตั้ง env ไว้
+fn get_env_var_or<T>(key: &str, default: T) -> Result<T>
+    where T: FromStr, T::Err: std::error::Error + Send + Sync + 'static
+{
+    match env::var(key) {
+        Ok(raw) => raw.parse::<T>().with_context(|| format!("{key} is invalid: {raw}")),
+        Err(_) => Ok(default),
+    }
+}
+
 fn load_stage() -> Result<Stage> {
     let stage_str = env::var("STAGE").unwrap_or_else(|_| "local".to_string());
     let stage = Stage::from_str(&stage_str).with_context(||
@@ -68,6 +111,82 @@ fn load_stage() -> Result<Stage> {
     Ok(stage)
 }
 
+/// AUTH_TOKEN_PRECEDENCE เช่น "Header,Cookie" (ค่าเริ่มต้น) หรือ "Cookie"
+fn load_token_precedence() -> Result<Vec<TokenSource>> {
+    let raw = env::var("AUTH_TOKEN_PRECEDENCE").unwrap_or_else(|_| "Header,Cookie".to_string());
+
+    let precedence = raw
+        .split(',')
+        .map(str::trim)
+        .filter(|source| !source.is_empty())
+        .map(|source|
+            TokenSource::from_str(source).with_context(||
+                format!("AUTH_TOKEN_PRECEDENCE is invalid: {raw}")
+            )
+        )
+        .collect::<Result<Vec<_>>>()?;
+
+    if precedence.is_empty() {
+        anyhow::bail!("AUTH_TOKEN_PRECEDENCE is invalid: {raw}");
+    }
+
+    Ok(precedence)
+}
+
+/// เส้นโค้งเลเวล: PROGRESSION_BASE_XP (100), PROGRESSION_GROWTH (1.5, อยู่ระหว่าง 1.0 ถึง 2.0)
+/// และโทษเมื่อเควสล้มเหลว PROGRESSION_FAILURE_PENALTY_PERCENT (0 = ปิด)
+fn load_progression() -> Result<Progression> {
+    let progression = Progression {
+        base_xp: get_env_var_or("PROGRESSION_BASE_XP", 100)?,
+        growth: get_env_var_or("PROGRESSION_GROWTH", 1.5)?,
+        failure_penalty_percent: get_env_var_or("PROGRESSION_FAILURE_PENALTY_PERCENT", 0)?,
+    };
+
+    if progression.base_xp < 1 {
+        anyhow::bail!("PROGRESSION_BASE_XP must be at least 1");
+    }
+
+    if !(MIN_GROWTH..=MAX_GROWTH).contains(&progression.growth) {
+        anyhow::bail!(
+            "PROGRESSION_GROWTH must be between {} and {}",
+            MIN_GROWTH,
+            MAX_GROWTH
+        );
+    }
+
+    if progression.failure_penalty_percent > 100 {
+        anyhow::bail!("PROGRESSION_FAILURE_PENALTY_PERCENT must not exceed 100");
+    }
+
+    Ok(progression)
+}
+
+/// เควสที่ถูกลบจะอยู่ในถังขยะ TRASH_RETENTION_DAYS วัน (ค่าเริ่มต้น 30) ก่อนถูกลบถาวร
+fn load_trash() -> Result<Trash> {
+    let trash = Trash {
+        retention_days: get_env_var_or("TRASH_RETENTION_DAYS", 30)?,
+    };
+
+    if trash.retention_days < 1 {
+        anyhow::bail!("TRASH_RETENTION_DAYS must be at least 1");
+    }
+
+    Ok(trash)
+}
+
+/// คำเชิญเข้าร่วมเควสจะหมดอายุหลังจาก INVITATION_TTL_HOURS ชั่วโมง (ค่าเริ่มต้น 72)
+fn load_invitations() -> Result<Invitations> {
+    let invitations = Invitations {
+        ttl_hours: get_env_var_or("INVITATION_TTL_HOURS", 72)?,
+    };
+
+    if invitations.ttl_hours < 1 {
+        anyhow::bail!("INVITATION_TTL_HOURS must be at least 1");
+    }
+
+    Ok(invitations)
+}
+
 pub fn get_stage() -> Stage {
     dotenvy::dotenv().ok();
 
@@ -1,4 +1,7 @@
-use crate::config::stage::Stage;
+use crate::{
+    config::{ stage::Stage, token_source::TokenSource },
+    domain::value_objects::progression::{ LevelCurve, ProgressionRules },
+};
 
 #[derive(Debug, Clone)]
 pub struct AppConfig {
@@ -7,6 +10,12 @@ pub struct AppConfig {
     pub database: Database,
     pub adventurers_secret: AdventurersSecret,
     pub guild_commanders_secret: GuildCommandersSecret,
+    pub authentication: Authentication,
+    pub scheduler: Scheduler,
+    pub progression: Progression,
+    pub trash: Trash,
+    pub board_cache: BoardCache,
+    pub invitations: Invitations,
 }
 
 #[derive(Debug, Clone)]
@@ -19,6 +28,9 @@ pub struct Server {
 #[derive(Debug, Clone)]
 pub struct Database {
     pub url: String,
+    pub max_connections: u32,
+    /// Seconds to wait for a free connection before giving up.
+    pub connection_timeout: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -31,4 +43,60 @@ pub struct AdventurersSecret {
 pub struct GuildCommandersSecret {
     pub secret: String,
     pub refresh_secret: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Authentication {
+    /// Token sources in the order they are tried, the first one present wins.
+    pub token_precedence: Vec<TokenSource>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Scheduler {
+    /// Seconds between two sweeps for overdue quests.
+    pub quest_deadlines_interval: u64,
+    /// Seconds between two purges of the quest trash.
+    pub quest_trash_interval: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Progression {
+    /// XP needed to go from level 1 to level 2.
+    pub base_xp: i64,
+    /// Factor by which the XP needed grows with every level.
+    pub growth: f64,
+    /// Percentage of a failed quest's rewards taken from its crew, 0 disables it.
+    pub failure_penalty_percent: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Trash {
+    /// Days a removed quest stays restorable before it is purged for good.
+    pub retention_days: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Invitations {
+    /// Hours an invitation can be accepted before it expires.
+    pub ttl_hours: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct BoardCache {
+    /// Seconds a cached board page is served, also its `max-age`. 0 disables the cache.
+    pub ttl: u64,
+    /// Most board pages kept at once.
+    pub capacity: usize,
+}
+
+impl Progression {
+    pub fn rules(&self) -> ProgressionRules {
+        ProgressionRules {
+            level_curve: LevelCurve {
+                base_xp: self.base_xp,
+                growth: self.growth,
+            },
+            failure_penalty_percent: self.failure_penalty_percent,
+        }
+    }
 }
\ No newline at end of file
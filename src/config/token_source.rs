@@ -0,0 +1,14 @@
+use strum_macros::{Display, EnumString};
+
+/// Where the authentication extractors look for an access token.
+#[derive(Display, EnumString, Debug, Clone, Copy, PartialEq)]
+#[strum(ascii_case_insensitive)]
+pub enum TokenSource {
+    /// `Authorization: Bearer <jwt>`
+    #[strum(serialize = "Header")]
+    Header,
+
+    /// The `act` cookie set on login.
+    #[strum(serialize = "Cookie")]
+    Cookie,
+}
@@ -1,16 +1,19 @@
 use chrono::NaiveDateTime;
 use diesel::{Selectable, prelude::{Identifiable, Insertable, Queryable}};
 
-use crate::infrastructure::postgres::schema::adventurers;
+use crate::{ domain::value_objects::ids::AdventurerId, infrastructure::postgres::schema::adventurers };
 
 #[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
 #[diesel(table_name = adventurers)]
 pub struct AdventurerEntity {
-    pub id: i32,
+    pub id: AdventurerId,
     pub username: String,
     pub password: String,
     pub created_at: NaiveDateTime,
-    pub updated_at: NaiveDateTime
+    pub updated_at: NaiveDateTime,
+    pub xp: i32,
+    pub level: i32,
+    pub gold: i32,
 }
 
 #[derive(Debug, Clone, Insertable, Queryable)]
@@ -1,12 +1,12 @@
 use chrono::NaiveDateTime;
 use diesel::{ Selectable, prelude::{ Identifiable, Insertable, Queryable } };
 
-use crate::infrastructure::postgres::schema::guild_commanders;
+use crate::{ domain::value_objects::ids::GuildCommanderId, infrastructure::postgres::schema::guild_commanders };
 
 #[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
 #[diesel(table_name = guild_commanders)]
 pub struct GuildCommanderEntity {
-    pub id: i32,
+    pub id: GuildCommanderId,
     pub username: String,
     pub password: String,
     pub created_at: NaiveDateTime,
@@ -1,3 +1,10 @@
 pub mod adventures;
 pub mod guild_commanders;
-pub mod quests;
\ No newline at end of file
+pub mod notifications;
+pub mod quest_applications;
+pub mod quest_invitations;
+pub mod quest_status_events;
+pub mod quest_waitlist;
+pub mod quests;
+pub mod sessions;
+pub mod tags;
\ No newline at end of file
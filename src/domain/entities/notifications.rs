@@ -0,0 +1,45 @@
+use chrono::NaiveDateTime;
+use diesel::{ Selectable, prelude::{ Identifiable, Insertable, Queryable } };
+
+use crate::{
+    domain::value_objects::{
+        ids::{ AdventurerId, NotificationId, QuestId },
+        notification_model::{ NotificationKind, NotificationModel },
+    },
+    infrastructure::postgres::schema::notifications,
+};
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = notifications)]
+pub struct NotificationEntity {
+    pub id: NotificationId,
+    pub adventurer_id: AdventurerId,
+    pub kind: NotificationKind,
+    pub quest_id: Option<QuestId>,
+    pub message: String,
+    pub read_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = notifications)]
+pub struct AddNotificationEntity {
+    pub adventurer_id: AdventurerId,
+    pub kind: NotificationKind,
+    pub quest_id: Option<QuestId>,
+    pub message: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl NotificationEntity {
+    pub fn to_model(&self) -> NotificationModel {
+        NotificationModel {
+            id: self.id,
+            kind: self.kind,
+            quest_id: self.quest_id,
+            message: self.message.clone(),
+            read_at: self.read_at,
+            created_at: self.created_at,
+        }
+    }
+}
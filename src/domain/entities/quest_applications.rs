@@ -0,0 +1,58 @@
+use chrono::NaiveDateTime;
+use diesel::{ Selectable, prelude::{ Identifiable, Insertable, Queryable } };
+
+use crate::{
+    domain::value_objects::{
+        ids::{ AdventurerId, QuestApplicationId, QuestId },
+        quest_applications::{ ApplicationStatus, QuestApplicationModel },
+    },
+    infrastructure::postgres::schema::quest_applications,
+};
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = quest_applications)]
+pub struct QuestApplicationEntity {
+    pub id: QuestApplicationId,
+    pub quest_id: QuestId,
+    pub adventurer_id: AdventurerId,
+    pub message: Option<String>,
+    pub status: ApplicationStatus,
+    pub decided_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = quest_applications)]
+pub struct AddQuestApplicationEntity {
+    pub quest_id: QuestId,
+    pub adventurer_id: AdventurerId,
+    pub message: Option<String>,
+    pub status: ApplicationStatus,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// An application together with who sent it, for the commander reviewing it.
+#[derive(Debug, Clone, Queryable)]
+pub struct QuestApplicationWithAdventurerEntity {
+    pub application: QuestApplicationEntity,
+    pub adventurer_username: String,
+    pub adventurer_level: i32,
+}
+
+impl QuestApplicationWithAdventurerEntity {
+    pub fn to_model(&self) -> QuestApplicationModel {
+        QuestApplicationModel {
+            id: self.application.id,
+            quest_id: self.application.quest_id,
+            adventurer_id: self.application.adventurer_id,
+            adventurer_username: self.adventurer_username.clone(),
+            adventurer_level: self.adventurer_level,
+            message: self.application.message.clone(),
+            status: self.application.status,
+            decided_at: self.application.decided_at,
+            created_at: self.application.created_at,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use chrono::NaiveDateTime;
+use diesel::{ Selectable, prelude::{ Identifiable, Insertable, Queryable } };
+
+use crate::{
+    domain::value_objects::{
+        ids::{ AdventurerId, GuildCommanderId, QuestId, QuestInvitationId },
+        quest_invitations::{ InvitationStatus, QuestInvitationModel },
+    },
+    infrastructure::postgres::schema::quest_invitations,
+};
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = quest_invitations)]
+pub struct QuestInvitationEntity {
+    pub id: QuestInvitationId,
+    pub quest_id: QuestId,
+    pub adventurer_id: AdventurerId,
+    pub guild_commander_id: GuildCommanderId,
+    pub status: InvitationStatus,
+    pub expires_at: NaiveDateTime,
+    pub responded_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = quest_invitations)]
+pub struct AddQuestInvitationEntity {
+    pub quest_id: QuestId,
+    pub adventurer_id: AdventurerId,
+    pub guild_commander_id: GuildCommanderId,
+    pub status: InvitationStatus,
+    pub expires_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// An invitation together with the name of the quest, for the invited adventurer.
+#[derive(Debug, Clone, Queryable)]
+pub struct QuestInvitationWithQuestEntity {
+    pub invitation: QuestInvitationEntity,
+    pub quest_name: String,
+}
+
+impl QuestInvitationWithQuestEntity {
+    pub fn to_model(&self) -> QuestInvitationModel {
+        QuestInvitationModel {
+            id: self.invitation.id,
+            quest_id: self.invitation.quest_id,
+            quest_name: self.quest_name.clone(),
+            guild_commander_id: self.invitation.guild_commander_id,
+            status: self.invitation.status,
+            expires_at: self.invitation.expires_at,
+            created_at: self.invitation.created_at,
+        }
+    }
+}
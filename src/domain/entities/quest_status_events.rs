@@ -0,0 +1,49 @@
+use chrono::NaiveDateTime;
+use diesel::{ Selectable, prelude::{ Identifiable, Insertable, Queryable } };
+
+use crate::{
+    domain::value_objects::{
+        ids::QuestId,
+        quest_status_event_model::QuestStatusEventModel,
+        quest_statuses::QuestStatus,
+    },
+    infrastructure::postgres::schema::quest_status_events,
+};
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = quest_status_events)]
+pub struct QuestStatusEventEntity {
+    pub id: i32,
+    pub quest_id: QuestId,
+    pub from_status: QuestStatus,
+    pub to_status: QuestStatus,
+    pub actor_role: String,
+    pub actor_id: Option<i32>,
+    pub reason: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable, Queryable)]
+#[diesel(table_name = quest_status_events)]
+pub struct AddQuestStatusEventEntity {
+    pub quest_id: QuestId,
+    pub from_status: QuestStatus,
+    pub to_status: QuestStatus,
+    pub actor_role: String,
+    pub actor_id: Option<i32>,
+    pub reason: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+impl QuestStatusEventEntity {
+    pub fn to_model(&self) -> QuestStatusEventModel {
+        QuestStatusEventModel {
+            from_status: self.from_status,
+            to_status: self.to_status,
+            actor_role: self.actor_role.clone(),
+            actor_id: self.actor_id,
+            reason: self.reason.clone(),
+            created_at: self.created_at,
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::Insertable;
+
+use crate::{
+    domain::value_objects::ids::{ AdventurerId, QuestId },
+    infrastructure::postgres::schema::quest_waitlist,
+};
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = quest_waitlist)]
+pub struct AddQuestWaitlistEntity {
+    pub quest_id: QuestId,
+    pub adventurer_id: AdventurerId,
+    pub created_at: NaiveDateTime,
+}
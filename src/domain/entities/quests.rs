@@ -1,18 +1,79 @@
 use chrono::NaiveDateTime;
-use diesel::{ Selectable, prelude::{ AsChangeset, Identifiable, Insertable, Queryable } };
+use diesel::{
+    NullableExpressionMethods,
+    Selectable,
+    dsl,
+    prelude::{ AsChangeset, Identifiable, Insertable, Queryable },
+};
 
-use crate::{domain::value_objects::quest_model::QuestModel, infrastructure::postgres::schema::{quest_adventurer_junction::adventurer_id, quests}};
+use crate::{
+    domain::value_objects::{
+        adventurer_rank::AdventurerRank,
+        ids::{ GuildCommanderId, QuestId },
+        join_policy::JoinPolicy,
+        quest_model::{ QuestModel, QuestSearchHitModel, TrashedQuestModel },
+        quest_statuses::QuestStatus,
+    },
+    infrastructure::postgres::schema::{ quest_adventurer_junction, quests },
+};
 
 #[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
 #[diesel(table_name = quests)]
 pub struct QuestEntity {
-    pub id: i32,
+    pub id: QuestId,
     pub name: String,
     pub description: Option<String>,
-    pub status: String,
-    pub guild_commander_id: i32,
+    pub status: QuestStatus,
+    pub guild_commander_id: GuildCommanderId,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    pub min_crew: i32,
+    pub max_crew: i32,
+    pub starts_at: Option<NaiveDateTime>,
+    pub due_at: Option<NaiveDateTime>,
+    pub reward_xp: i32,
+    pub reward_gold: i32,
+    pub min_level: i32,
+    pub min_rank: Option<AdventurerRank>,
+    pub version: i32,
+    pub join_policy: JoinPolicy,
+}
+
+/// A quest together with the size of its crew, loaded from a single grouped
+/// `LEFT JOIN` on `quest_adventurer_junction`.
+#[derive(Debug, Clone, Selectable, Queryable)]
+#[diesel(table_name = quests)]
+pub struct QuestWithCrewCountEntity {
+    #[diesel(embed)]
+    pub quest: QuestEntity,
+    #[diesel(select_expression = dsl::count(quest_adventurer_junction::adventurer_id.nullable()))]
+    #[diesel(select_expression_type = dsl::count<dsl::Nullable<quest_adventurer_junction::adventurer_id>>)]
+    pub crew_count: i64,
+}
+
+/// A removed quest still waiting in the trash.
+#[derive(Debug, Clone, Selectable, Queryable)]
+#[diesel(table_name = quests)]
+pub struct TrashedQuestEntity {
+    #[diesel(embed)]
+    pub quest: QuestEntity,
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+/// Full-text search rank and `ts_headline` snippets of a quest.
+#[derive(Debug, Clone, Queryable)]
+pub struct QuestSearchHitEntity {
+    pub quest_id: QuestId,
+    pub rank: f32,
+    pub name_highlight: String,
+    pub description_highlight: Option<String>,
+}
+
+/// A quest whose name is similar to a fuzzy search, see `QuestViewingRepository::fuzzy_search`.
+#[derive(Debug, Clone, Queryable)]
+pub struct QuestSimilarityEntity {
+    pub quest: QuestWithCrewCountEntity,
+    pub similarity: f32,
 }
 
 #[derive(Debug, Clone, Insertable, Queryable)]
@@ -20,10 +81,19 @@ pub struct QuestEntity {
 pub struct AddQuestEntity {
     pub name: String,
     pub description: Option<String>,
-    pub status: String,
-    pub guild_commander_id: i32,
+    pub status: QuestStatus,
+    pub guild_commander_id: GuildCommanderId,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    pub min_crew: i32,
+    pub max_crew: i32,
+    pub starts_at: Option<NaiveDateTime>,
+    pub due_at: Option<NaiveDateTime>,
+    pub reward_xp: i32,
+    pub reward_gold: i32,
+    pub min_level: i32,
+    pub min_rank: Option<AdventurerRank>,
+    pub join_policy: JoinPolicy,
 }
 
 #[derive(Debug, Clone, Queryable, AsChangeset)]
@@ -31,21 +101,69 @@ pub struct AddQuestEntity {
 pub struct EditQuestEntity {
     pub name: Option<String>,
     pub description: Option<String>,
-    pub guild_commander_id: i32,
+    pub min_crew: Option<i32>,
+    pub max_crew: Option<i32>,
+    pub reward_xp: Option<i32>,
+    pub reward_gold: Option<i32>,
+    pub min_level: Option<i32>,
+    /// `Some(None)` clears the requirement, `None` leaves it unchanged.
+    pub min_rank: Option<Option<AdventurerRank>>,
+    pub join_policy: Option<JoinPolicy>,
     pub updated_at: NaiveDateTime,
 }
 
 impl QuestEntity {
-    pub fn to_model(&self, adventurer_count: i64) -> QuestModel {
+    pub fn to_model(&self, adventurer_count: i64, tags: Vec<String>) -> QuestModel {
         QuestModel {
             id: self.id,
             name: self.name.clone(),
             description: self.description.clone(),
-            status: self.status.clone(),
+            status: self.status,
             guild_commander_id: self.guild_commander_id,
             created_at: self.created_at,
             updated_at: self.updated_at,
+            min_crew: self.min_crew,
+            max_crew: self.max_crew,
+            starts_at: self.starts_at,
+            due_at: self.due_at,
+            reward_xp: self.reward_xp,
+            reward_gold: self.reward_gold,
+            min_level: self.min_level,
+            min_rank: self.min_rank,
+            version: self.version,
+            join_policy: self.join_policy,
             adventurer_count,
+            tags,
+            search: None,
         }
     }
-}
\ No newline at end of file
+}
+
+impl QuestWithCrewCountEntity {
+    pub fn to_model(&self, tags: Vec<String>) -> QuestModel {
+        self.quest.to_model(self.crew_count, tags)
+    }
+}
+
+impl QuestSearchHitEntity {
+    pub fn to_model(&self) -> QuestSearchHitModel {
+        QuestSearchHitModel {
+            rank: self.rank,
+            name_highlight: self.name_highlight.clone(),
+            description_highlight: self.description_highlight.clone(),
+        }
+    }
+}
+
+impl TrashedQuestEntity {
+    /// Removed quests never have a crew, see `QuestOpsUseCase::remove`.
+    pub fn to_model(&self, tags: Vec<String>, retention: chrono::Duration) -> TrashedQuestModel {
+        let deleted_at = self.deleted_at.unwrap_or(self.quest.updated_at);
+
+        TrashedQuestModel {
+            quest: self.quest.to_model(0, tags),
+            deleted_at,
+            purge_at: deleted_at + retention,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use chrono::NaiveDateTime;
+use diesel::{ Selectable, prelude::{ Identifiable, Insertable, Queryable } };
+use uuid::Uuid;
+
+use crate::infrastructure::postgres::schema::sessions;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = sessions)]
+pub struct SessionEntity {
+    pub id: i32,
+    pub family_id: Uuid,
+    pub role: String,
+    pub subject_id: i32,
+    pub refresh_token_hash: String,
+    pub expires_at: NaiveDateTime,
+    pub rotated_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable, Queryable)]
+#[diesel(table_name = sessions)]
+pub struct AddSessionEntity {
+    pub family_id: Uuid,
+    pub role: String,
+    pub subject_id: i32,
+    pub refresh_token_hash: String,
+    pub expires_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
@@ -0,0 +1,51 @@
+use chrono::NaiveDateTime;
+use diesel::{ Selectable, prelude::{ Identifiable, Insertable, Queryable } };
+
+use crate::{
+    domain::value_objects::{ ids::QuestId, tag_model::TagUsageModel },
+    infrastructure::postgres::schema::{ quest_tags, tags },
+};
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = tags)]
+pub struct TagEntity {
+    pub id: i32,
+    pub name: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = tags)]
+pub struct AddTagEntity {
+    pub name: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable, Queryable)]
+#[diesel(table_name = quest_tags)]
+pub struct QuestTagEntity {
+    pub quest_id: QuestId,
+    pub tag_id: i32,
+}
+
+/// A tag name attached to a quest, used to fill in `QuestModel::tags`.
+#[derive(Debug, Clone, Queryable)]
+pub struct QuestTagNameEntity {
+    pub quest_id: QuestId,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Queryable)]
+pub struct TagUsageEntity {
+    pub name: String,
+    pub quest_count: i64,
+}
+
+impl TagUsageEntity {
+    pub fn to_model(&self) -> TagUsageModel {
+        TagUsageModel {
+            name: self.name.clone(),
+            quest_count: self.quest_count,
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use diesel::result::{ DatabaseErrorKind, Error as DieselError };
+use thiserror::Error;
+use tracing::warn;
+
+pub type AppResult<T> = std::result::Result<T, AppError>;
+
+#[derive(Debug, Error)]
+pub enum AppError {
+    #[error("{0}")]
+    NotFound(String),
+
+    #[error("{0}")]
+    Conflict(String),
+
+    #[error("{0}")]
+    Forbidden(String),
+
+    #[error("{0}")]
+    InvalidTransition(String),
+
+    #[error("{0}")]
+    Validation(String),
+
+    #[error("{0}")]
+    Unauthorized(String),
+
+    #[error("{0}")]
+    PreconditionFailed(String),
+
+    #[error("{0}")]
+    PreconditionRequired(String),
+
+    #[error(transparent)]
+    Internal(#[from] anyhow::Error),
+}
+
+impl AppError {
+    /// Stable machine readable code, clients branch on this instead of the message.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::NotFound(_) => "NOT_FOUND",
+            AppError::Conflict(_) => "CONFLICT",
+            AppError::Forbidden(_) => "FORBIDDEN",
+            AppError::InvalidTransition(_) => "INVALID_TRANSITION",
+            AppError::Validation(_) => "VALIDATION",
+            AppError::Unauthorized(_) => "UNAUTHORIZED",
+            AppError::PreconditionFailed(_) => "PRECONDITION_FAILED",
+            AppError::PreconditionRequired(_) => "PRECONDITION_REQUIRED",
+            AppError::Internal(_) => "INTERNAL",
+        }
+    }
+}
+
+impl From<DieselError> for AppError {
+    fn from(err: DieselError) -> Self {
+        match err {
+            DieselError::NotFound => AppError::NotFound("Record not found".to_string()),
+            // Constraint details stay in the logs, they leak table and constraint names.
+            DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, info) => {
+                warn!(
+                    constraint = info.constraint_name(),
+                    details = info.details(),
+                    "unique violation"
+                );
+                AppError::Conflict("Resource already exists".to_string())
+            }
+            DieselError::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, info) => {
+                warn!(
+                    constraint = info.constraint_name(),
+                    details = info.details(),
+                    "foreign key violation"
+                );
+                AppError::Validation("Referenced resource does not exist".to_string())
+            }
+            err => AppError::Internal(err.into()),
+        }
+    }
+}
+
+impl From<diesel::r2d2::PoolError> for AppError {
+    fn from(err: diesel::r2d2::PoolError) -> Self {
+        AppError::Internal(err.into())
+    }
+}
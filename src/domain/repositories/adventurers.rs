@@ -1,14 +1,16 @@
-use anyhow::Result;
 use axum::async_trait;
 use mockall::automock;
 
 use crate::domain::{
     entities::adventures::{ AdventurerEntity, RegisterAdventurerEntity },
+    errors::AppResult,
+    value_objects::ids::AdventurerId,
 };
 
 #[async_trait]
 #[automock]
 pub trait AdventurersRepository {
-    async fn register(&self, register_adventurer_entity: RegisterAdventurerEntity) -> Result<i32>;
-    async fn find_by_username(&self, username: String) -> Result<AdventurerEntity>;
+    async fn register(&self, register_adventurer_entity: RegisterAdventurerEntity) -> AppResult<AdventurerId>;
+    async fn find_by_username(&self, username: String) -> AppResult<AdventurerEntity>;
+    async fn find_by_id(&self, adventurer_id: AdventurerId) -> AppResult<AdventurerEntity>;
 }
@@ -1,12 +1,71 @@
-use anyhow::Result;
 use axum::async_trait;
+use chrono::NaiveDateTime;
 use mockall::automock;
 
-use crate::domain::value_objects::quest_adventurer_junction::QuestAdventurerJunction;
+use crate::domain::{
+    entities::quest_applications::{
+        AddQuestApplicationEntity,
+        QuestApplicationEntity,
+        QuestApplicationWithAdventurerEntity,
+    },
+    entities::quest_invitations::{ AddQuestInvitationEntity, QuestInvitationWithQuestEntity },
+    errors::AppResult,
+    value_objects::{
+        ids::{ AdventurerId, QuestApplicationId, QuestId, QuestInvitationId },
+        quest_adventurer_junction::QuestAdventurerJunction,
+        quest_applications::ApplicationStatus,
+        quest_waitlist::JoinOutcome,
+    },
+};
 
 #[async_trait]
 #[automock]
 pub trait CrewSwitchboardRepository {
-    async fn join(&self, junction_body: QuestAdventurerJunction) -> Result<()>;
-    async fn leave(&self, junction_body: QuestAdventurerJunction) -> Result<()>;
+    /// Adds the adventurer to the crew in one transaction that locks the quest
+    /// and checks the join policy, capacity, status, level and rank
+    /// requirements and duplicate membership before inserting. With
+    /// `waitlist` a full quest queues the adventurer instead of failing.
+    async fn join(&self, junction_body: QuestAdventurerJunction, waitlist: bool) -> AppResult<JoinOutcome>;
+    /// Removes the adventurer from the crew, checking the status under the same
+    /// lock. While the quest stays open to join, the first waiting adventurer who
+    /// meets the requirements takes the freed slot and is notified, in the same
+    /// transaction.
+    async fn leave(&self, junction_body: QuestAdventurerJunction) -> AppResult<()>;
+    async fn leave_waitlist(&self, junction_body: QuestAdventurerJunction) -> AppResult<()>;
+    /// Records a pending application under the quest lock, after the same
+    /// checks as `join` so hopeless applications are turned away early.
+    async fn apply(&self, application: AddQuestApplicationEntity) -> AppResult<QuestApplicationId>;
+    async fn withdraw(&self, quest_id: QuestId, adventurer_id: AdventurerId) -> AppResult<()>;
+    async fn applications(
+        &self,
+        quest_id: QuestId,
+        status: Option<ApplicationStatus>
+    ) -> AppResult<Vec<QuestApplicationWithAdventurerEntity>>;
+    async fn find_application(&self, application_id: QuestApplicationId) -> AppResult<QuestApplicationEntity>;
+    /// Adds the applicant to the crew with every check `join` makes, except the join policy.
+    async fn approve(&self, application_id: QuestApplicationId) -> AppResult<()>;
+    async fn reject(&self, application_id: QuestApplicationId) -> AppResult<()>;
+    /// Records a pending invitation under the quest lock after the same checks
+    /// as `approve`. Expired invitations to the same adventurer are closed first.
+    async fn invite(&self, invitation: AddQuestInvitationEntity) -> AppResult<QuestInvitationId>;
+    /// Pending invitations of the adventurer that have not expired by `now`.
+    async fn invitations(
+        &self,
+        adventurer_id: AdventurerId,
+        now: NaiveDateTime
+    ) -> AppResult<Vec<QuestInvitationWithQuestEntity>>;
+    /// Adds the invited adventurer to the crew with every check `join` makes,
+    /// except the join policy.
+    async fn accept_invitation(
+        &self,
+        invitation_id: QuestInvitationId,
+        adventurer_id: AdventurerId,
+        now: NaiveDateTime
+    ) -> AppResult<()>;
+    async fn decline_invitation(
+        &self,
+        invitation_id: QuestInvitationId,
+        adventurer_id: AdventurerId,
+        now: NaiveDateTime
+    ) -> AppResult<()>;
 }
\ No newline at end of file
@@ -1,9 +1,10 @@
-use anyhow::Result;
 use axum::async_trait;
 use mockall::automock;
 
 use crate::domain::{
     entities::guild_commanders::{ GuildCommanderEntity, RegisterGuildCommanderEntity },
+    errors::AppResult,
+    value_objects::ids::GuildCommanderId,
 };
 
 #[async_trait]
@@ -12,6 +13,6 @@ pub trait GuildCommanderRepository {
     async fn register(
         &self,
         register_guild_commander_entity: RegisterGuildCommanderEntity
-    ) -> Result<i32>;
-    async fn find_by_username(&self, username: String) -> Result<GuildCommanderEntity>;
+    ) -> AppResult<GuildCommanderId>;
+    async fn find_by_username(&self, username: String) -> AppResult<GuildCommanderEntity>;
 }
@@ -1,11 +1,30 @@
-use anyhow::Result;
 use axum::async_trait;
 use mockall::automock;
 
+use crate::domain::{
+    entities::quest_status_events::QuestStatusEventEntity,
+    errors::AppResult,
+    value_objects::{
+        ids::{ GuildCommanderId, QuestId },
+        progression::ProgressionRules,
+        quest_statuses::QuestTransition,
+    },
+};
+
 #[async_trait]
 #[automock]
 pub trait JourneyLedgerRepository{
-    async fn in_journey(&self, quest_id: i32, guild_commander_id: i32) -> Result<i32>;
-    async fn to_complete(&self, quest_id: i32, guild_commander_id: i32) -> Result<i32>;
-    async fn to_failed(&self, quest_id: i32, guild_commander_id: i32) -> Result<i32>;
-}
\ No newline at end of file
+    /// Moves the quest along `transition`, applies its effects and records the
+    /// status event atomically. Fails with `Conflict` when the quest is no longer
+    /// in `transition.from`.
+    async fn transition(
+        &self,
+        quest_id: QuestId,
+        guild_commander_id: GuildCommanderId,
+        transition: QuestTransition,
+        reason: Option<String>,
+        progression: ProgressionRules
+    ) -> AppResult<QuestId>;
+    /// Status events of the quest, oldest first.
+    async fn history(&self, quest_id: QuestId) -> AppResult<Vec<QuestStatusEventEntity>>;
+}
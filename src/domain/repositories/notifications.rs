@@ -0,0 +1,21 @@
+use axum::async_trait;
+use mockall::automock;
+
+use crate::domain::{
+    entities::notifications::NotificationEntity,
+    errors::AppResult,
+    value_objects::ids::{ AdventurerId, NotificationId },
+};
+
+#[async_trait]
+#[automock]
+pub trait NotificationsRepository {
+    async fn list(
+        &self,
+        adventurer_id: AdventurerId,
+        unread_only: bool,
+        limit: i64
+    ) -> AppResult<Vec<NotificationEntity>>;
+    /// Another adventurer's notification is reported as missing.
+    async fn mark_read(&self, notification_id: NotificationId, adventurer_id: AdventurerId) -> AppResult<()>;
+}
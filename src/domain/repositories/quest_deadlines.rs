@@ -0,0 +1,33 @@
+use axum::async_trait;
+use chrono::NaiveDateTime;
+use mockall::automock;
+
+use crate::domain::{
+    errors::AppResult,
+    value_objects::{ ids::QuestId, progression::ProgressionRules, quest_statuses::QuestTransition },
+};
+
+#[async_trait]
+#[automock]
+pub trait QuestDeadlinesRepository {
+    /// Moves every live quest in `transition.from` whose `due_at` is before `now`
+    /// along `transition` as the system actor, in a single transaction.
+    /// Returns `None` without touching anything when another instance is
+    /// already sweeping.
+    async fn sweep_overdue(
+        &self,
+        transition: QuestTransition,
+        now: NaiveDateTime,
+        reason: String,
+        progression: ProgressionRules
+    ) -> AppResult<Option<Vec<QuestId>>>;
+    /// Moves every live quest in `transition.from` whose `starts_at` is before
+    /// `now` and whose crew is still below `min_crew`, like `sweep_overdue`.
+    async fn sweep_unstarted(
+        &self,
+        transition: QuestTransition,
+        now: NaiveDateTime,
+        reason: String,
+        progression: ProgressionRules
+    ) -> AppResult<Option<Vec<QuestId>>>;
+}
@@ -1,15 +1,46 @@
-use anyhow::Result;
 use axum::async_trait;
+use chrono::NaiveDateTime;
 use mockall::automock;
 
 use crate::{
-    domain::entities::quests::{ AddQuestEntity, EditQuestEntity },
+    domain::{
+        entities::quests::{ AddQuestEntity, EditQuestEntity, TrashedQuestEntity },
+        errors::AppResult,
+        value_objects::ids::{ GuildCommanderId, QuestId },
+    },
 };
 
 #[async_trait]
 #[automock]
 pub trait QuestOpsRepository {
-    async fn add(&self, add_quest_entity: AddQuestEntity) -> Result<i32>;
-    async fn edit(&self, quest_id: i32, edit_quest_entity: EditQuestEntity) -> Result<i32>;
-    async fn remove(&self, quest_id: i32, guild_commander_id: i32) -> Result<()>;
+    /// Inserts the quest together with its tags, creating tags seen for the first time.
+    async fn add(&self, add_quest_entity: AddQuestEntity, tags: Vec<String>) -> AppResult<QuestId>;
+    /// Updates the quest and, when `tags` is given, replaces its tags. With
+    /// `expected_version` the update only happens if the quest is still at that
+    /// version, `PreconditionFailed` otherwise.
+    async fn edit(
+        &self,
+        quest_id: QuestId,
+        edit_quest_entity: EditQuestEntity,
+        tags: Option<Vec<String>>,
+        expected_version: Option<i32>
+    ) -> AppResult<QuestId>;
+    /// Soft deletes the quest by setting `deleted_at`, `Conflict` when it is no
+    /// longer the commander's, editable and without crew.
+    async fn remove(&self, quest_id: QuestId, guild_commander_id: GuildCommanderId) -> AppResult<()>;
+    /// The commander's removed quests, most recently removed first.
+    async fn trash(&self, guild_commander_id: GuildCommanderId) -> AppResult<Vec<TrashedQuestEntity>>;
+    async fn find_trashed(&self, quest_id: QuestId) -> AppResult<TrashedQuestEntity>;
+    /// Clears `deleted_at` in one transaction with the due date check, `NotFound`
+    /// when the quest is no longer in the commander's trash and `Conflict` when
+    /// its due date passed before `now`.
+    async fn restore(
+        &self,
+        quest_id: QuestId,
+        guild_commander_id: GuildCommanderId,
+        now: NaiveDateTime
+    ) -> AppResult<QuestId>;
+    /// Hard deletes quests removed before `deleted_before`. Crew, tags and
+    /// status history go with them through `ON DELETE CASCADE`.
+    async fn purge_trashed(&self, deleted_before: NaiveDateTime) -> AppResult<Vec<QuestId>>;
 }
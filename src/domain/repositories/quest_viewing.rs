@@ -1,16 +1,52 @@
-use anyhow::Result;
 use axum::async_trait;
 use mockall::automock;
 
 use crate::{ 
-    domain::entities::quests::QuestEntity,
-    domain::value_objects::board_checking_filter::BoardCheckingFilter,
+    domain::entities::{
+        quests::{ QuestEntity, QuestSearchHitEntity, QuestSimilarityEntity, QuestWithCrewCountEntity },
+        tags::{ QuestTagNameEntity, TagUsageEntity },
+    },
+    domain::errors::AppResult,
+    domain::value_objects::{
+        board_checking_filter::BoardCheckingFilter,
+        board_cursor::BoardCursor,
+        ids::{ AdventurerId, QuestId },
+    },
  };
 
 #[async_trait]
 #[automock]
 pub trait QuestViewingRepository {
-    async fn view_details(&self, quest_id: i32) -> Result<QuestEntity>;
-    async fn board_checking(&self, filter: &BoardCheckingFilter) -> Result<Vec<QuestEntity>>;
-    async fn adventurers_counting_by_quest_id(&self, quest_id: i32) -> Result<i64>;
+    async fn view_details(&self, quest_id: QuestId) -> AppResult<QuestEntity>;
+    async fn view_details_with_crew_count(&self, quest_id: QuestId) -> AppResult<QuestWithCrewCountEntity>;
+    /// Up to `limit` quests matching `filter`, in the filter's sort order and
+    /// strictly after `cursor` when one is given. With `eligible_for`, quests that
+    /// adventurer cannot join are left out.
+    async fn board_checking(
+        &self,
+        filter: &BoardCheckingFilter,
+        eligible_for: Option<AdventurerId>,
+        cursor: Option<BoardCursor>,
+        limit: i64
+    ) -> AppResult<Vec<QuestWithCrewCountEntity>>;
+    /// Number of quests matching `filter`, ignoring pagination.
+    async fn board_counting(
+        &self,
+        filter: &BoardCheckingFilter,
+        eligible_for: Option<AdventurerId>
+    ) -> AppResult<i64>;
+    async fn adventurers_counting_by_quest_id(&self, quest_id: QuestId) -> AppResult<i64>;
+    /// Search rank and highlighted snippets of the given quests for `q`.
+    async fn search_hits(&self, quest_ids: Vec<QuestId>, q: String) -> AppResult<Vec<QuestSearchHitEntity>>;
+    /// Board visible quests whose name is at least `threshold` word-similar to
+    /// `q`, most similar first.
+    async fn fuzzy_search(&self, q: String, threshold: f32, limit: i64) -> AppResult<Vec<QuestSimilarityEntity>>;
+    /// The word of a board visible quest name most similar to `word`, if any
+    /// reaches `threshold`.
+    async fn closest_name_word(&self, word: String, threshold: f32) -> AppResult<Option<String>>;
+    /// Tag names of the given quests, alphabetical per quest.
+    async fn tags_by_quest_ids(&self, quest_ids: Vec<QuestId>) -> AppResult<Vec<QuestTagNameEntity>>;
+    /// Every tag used by a board visible quest with the number of such quests,
+    /// most used first.
+    async fn tag_usage(&self) -> AppResult<Vec<TagUsageEntity>>;
 }
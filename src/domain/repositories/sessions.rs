@@ -0,0 +1,20 @@
+use axum::async_trait;
+use mockall::automock;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::sessions::{ AddSessionEntity, SessionEntity },
+    errors::AppResult,
+};
+
+#[async_trait]
+#[automock]
+pub trait SessionsRepository {
+    async fn create(&self, add_session_entity: AddSessionEntity) -> AppResult<i32>;
+    async fn find_by_token_hash(&self, refresh_token_hash: String) -> AppResult<SessionEntity>;
+    /// Marks `session_id` as used and stores its successor in one transaction.
+    /// Fails with `Conflict` when the session was already rotated or revoked.
+    async fn rotate(&self, session_id: i32, add_session_entity: AddSessionEntity) -> AppResult<i32>;
+    async fn revoke_family(&self, family_id: Uuid) -> AppResult<()>;
+    async fn revoke_all_by_subject(&self, role: String, subject_id: i32) -> AppResult<()>;
+}
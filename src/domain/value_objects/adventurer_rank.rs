@@ -0,0 +1,84 @@
+use std::{ io::Write, str::FromStr };
+
+use diesel::{
+    deserialize::{ self, FromSql, FromSqlRow },
+    expression::AsExpression,
+    pg::{ Pg, PgValue },
+    serialize::{ self, IsNull, Output, ToSql },
+    sql_types::Varchar,
+};
+use serde::{ Deserialize, Serialize };
+use strum_macros::{ Display, EnumString };
+
+/// Rank tiers an adventurer climbs through by levelling up, lowest first.
+#[derive(
+    Display,
+    EnumString,
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    AsExpression,
+    FromSqlRow
+)]
+#[diesel(sql_type = Varchar)]
+pub enum AdventurerRank {
+    Bronze,
+    Silver,
+    Gold,
+    Platinum,
+    Mythril,
+}
+
+pub const ADVENTURER_RANKS: [AdventurerRank; 5] = [
+    AdventurerRank::Bronze,
+    AdventurerRank::Silver,
+    AdventurerRank::Gold,
+    AdventurerRank::Platinum,
+    AdventurerRank::Mythril,
+];
+
+impl AdventurerRank {
+    /// Level at which the rank is reached.
+    pub fn min_level(&self) -> i32 {
+        match self {
+            AdventurerRank::Bronze => 1,
+            AdventurerRank::Silver => 5,
+            AdventurerRank::Gold => 10,
+            AdventurerRank::Platinum => 20,
+            AdventurerRank::Mythril => 35,
+        }
+    }
+
+    pub fn for_level(level: i32) -> Self {
+        ADVENTURER_RANKS.into_iter()
+            .rev()
+            .find(|rank| level >= rank.min_level())
+            .unwrap_or(AdventurerRank::Bronze)
+    }
+
+    /// This rank and every rank below it.
+    pub fn and_below(&self) -> Vec<AdventurerRank> {
+        ADVENTURER_RANKS.into_iter().filter(|rank| rank <= self).collect()
+    }
+}
+
+impl ToSql<Varchar, Pg> for AdventurerRank {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.to_string().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Varchar, Pg> for AdventurerRank {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let value = <String as FromSql<Varchar, Pg>>::from_sql(bytes)?;
+        AdventurerRank::from_str(&value).map_err(|_| format!("Unknown adventurer rank: {}", value).into())
+    }
+}
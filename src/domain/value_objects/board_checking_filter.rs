@@ -1,10 +1,76 @@
 use serde::{Deserialize, Serialize};
 
-use crate::domain::value_objects::quest_statuses::QuestStatus;
+use crate::domain::value_objects::{ quest_statuses::QuestStatus, tag_model::{ TagMatch, normalize_tag } };
 
+pub const DEFAULT_BOARD_LIMIT: i64 = 20;
+pub const MAX_BOARD_LIMIT: i64 = 100;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BoardSort {
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+    Name,
+    CrewSize,
+    /// Full-text search rank, only together with `q`.
+    Relevance,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct BoardCheckingFilter {
     pub name: Option<String>,
-    pub status: Option<QuestStatus>
-}
\ No newline at end of file
+    /// Full-text search over name and description in web search syntax.
+    pub q: Option<String>,
+    pub status: Option<QuestStatus>,
+    /// Defaults to `relevance` when searching with `q`, `created_at` otherwise.
+    pub sort: Option<BoardSort>,
+    #[serde(default)]
+    pub direction: SortDirection,
+    /// Opaque cursor taken from `next_cursor` of the previous page.
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+    /// Only quests the calling adventurer could join right now, needs an adventurer token.
+    #[serde(default)]
+    pub eligible_for_me: bool,
+    /// Comma separated tag names, e.g. `tags=escort,night`.
+    pub tags: Option<String>,
+    #[serde(default)]
+    pub tag_match: TagMatch,
+}
+
+impl BoardCheckingFilter {
+    /// The search terms, `None` when `q` is missing or blank.
+    pub fn search_query(&self) -> Option<&str> {
+        self.q.as_deref().map(str::trim).filter(|q| !q.is_empty())
+    }
+
+    pub fn sort(&self) -> BoardSort {
+        match (self.sort, self.search_query()) {
+            (Some(sort), _) => sort,
+            (None, Some(_)) => BoardSort::Relevance,
+            (None, None) => BoardSort::CreatedAt,
+        }
+    }
+
+    /// The requested tags, normalized the same way they are stored.
+    pub fn tag_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+
+        for name in self.tags.iter().flat_map(|tags| tags.split(',')).map(normalize_tag) {
+            if !name.is_empty() && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+
+        names
+    }
+}
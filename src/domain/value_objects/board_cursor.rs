@@ -0,0 +1,147 @@
+use base64::{ Engine, engine::general_purpose::URL_SAFE_NO_PAD };
+use chrono::NaiveDateTime;
+use serde::{ Deserialize, Serialize };
+
+use crate::domain::{
+    entities::quests::QuestWithCrewCountEntity,
+    errors::{ AppError, AppResult },
+    value_objects::{ board_checking_filter::{ BoardSort, SortDirection }, ids::QuestId },
+};
+
+/// Sort key of the last quest on a page. The variant must match the sort the
+/// cursor is used with.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum BoardCursorKey {
+    CreatedAt(NaiveDateTime),
+    UpdatedAt(NaiveDateTime),
+    Name(String),
+    CrewSize(i64),
+    Relevance(f32),
+}
+
+/// Keyset position on the board, handed to clients as an opaque base64 string.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BoardCursor {
+    pub key: BoardCursorKey,
+    pub direction: SortDirection,
+    pub id: QuestId,
+}
+
+impl BoardCursorKey {
+    pub fn sort(&self) -> BoardSort {
+        match self {
+            BoardCursorKey::CreatedAt(_) => BoardSort::CreatedAt,
+            BoardCursorKey::UpdatedAt(_) => BoardSort::UpdatedAt,
+            BoardCursorKey::Name(_) => BoardSort::Name,
+            BoardCursorKey::CrewSize(_) => BoardSort::CrewSize,
+            BoardCursorKey::Relevance(_) => BoardSort::Relevance,
+        }
+    }
+}
+
+impl BoardCursor {
+    /// `rank` is the quest's search rank, only read for `BoardSort::Relevance`.
+    pub fn after(
+        sort: BoardSort,
+        direction: SortDirection,
+        quest: &QuestWithCrewCountEntity,
+        rank: Option<f32>
+    ) -> Self {
+        let key = match sort {
+            BoardSort::CreatedAt => BoardCursorKey::CreatedAt(quest.quest.created_at),
+            BoardSort::UpdatedAt => BoardCursorKey::UpdatedAt(quest.quest.updated_at),
+            BoardSort::Name => BoardCursorKey::Name(quest.quest.name.clone()),
+            BoardSort::CrewSize => BoardCursorKey::CrewSize(quest.crew_count),
+            BoardSort::Relevance => BoardCursorKey::Relevance(rank.unwrap_or_default()),
+        };
+
+        Self { key, direction, id: quest.quest.id }
+    }
+
+    pub fn encode(&self) -> AppResult<String> {
+        let json = serde_json::to_vec(self).map_err(anyhow::Error::from)?;
+
+        Ok(URL_SAFE_NO_PAD.encode(json))
+    }
+
+    /// Decodes a cursor and checks it was issued for the same sort and direction.
+    pub fn decode(cursor: &str, sort: BoardSort, direction: SortDirection) -> AppResult<Self> {
+        let invalid = || AppError::Validation("Invalid cursor".to_string());
+
+        let json = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| invalid())?;
+        let cursor: BoardCursor = serde_json::from_slice(&json).map_err(|_| invalid())?;
+
+        if cursor.key.sort() != sort || cursor.direction != direction {
+            return Err(AppError::Validation("Cursor does not match the requested sort".to_string()));
+        }
+
+        Ok(cursor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cursor(key: BoardCursorKey) -> BoardCursor {
+        BoardCursor { key, direction: SortDirection::Asc, id: QuestId(42) }
+    }
+
+    #[test]
+    fn round_trips_every_key() {
+        let created_at = chrono::DateTime::from_timestamp(1_700_000_000, 123_456_000).unwrap().naive_utc();
+
+        for key in [
+            BoardCursorKey::CreatedAt(created_at),
+            BoardCursorKey::UpdatedAt(created_at),
+            BoardCursorKey::Name("Slay the \"dragon\" 🐉".to_string()),
+            BoardCursorKey::CrewSize(3),
+            BoardCursorKey::Relevance(0.25),
+        ] {
+            let sort = key.sort();
+            let original = cursor(key);
+
+            let decoded = BoardCursor::decode(&original.encode().unwrap(), sort, SortDirection::Asc).unwrap();
+
+            assert_eq!(decoded, original);
+        }
+    }
+
+    #[test]
+    fn encoded_cursor_is_url_safe() {
+        let encoded = cursor(BoardCursorKey::Name("???>>>".to_string())).encode().unwrap();
+
+        assert!(encoded.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+    }
+
+    #[test]
+    fn rejects_a_cursor_for_another_sort_or_direction() {
+        let encoded = cursor(BoardCursorKey::CrewSize(3)).encode().unwrap();
+
+        let other_sort = BoardCursor::decode(&encoded, BoardSort::Name, SortDirection::Asc);
+        let other_direction = BoardCursor::decode(&encoded, BoardSort::CrewSize, SortDirection::Desc);
+
+        assert!(matches!(other_sort, Err(AppError::Validation(_))));
+        assert!(matches!(other_direction, Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn rejects_tampered_cursors() {
+        let mut encoded = cursor(BoardCursorKey::CrewSize(3)).encode().unwrap();
+        encoded.truncate(encoded.len() - 4);
+
+        let tampered = [
+            "not base64!".to_string(),
+            encoded,
+            URL_SAFE_NO_PAD.encode(b"{\"key\":{\"CrewSize\":\"three\"},\"direction\":\"asc\",\"id\":42}"),
+            URL_SAFE_NO_PAD.encode(b"{\"key\":{\"Unknown\":1},\"direction\":\"asc\",\"id\":42}"),
+            String::new(),
+        ];
+
+        for cursor in tampered {
+            let result = BoardCursor::decode(&cursor, BoardSort::CrewSize, SortDirection::Asc);
+
+            assert!(matches!(result, Err(AppError::Validation(_))), "accepted {:?}", cursor);
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use serde::{ Deserialize, Serialize };
+
+use crate::domain::value_objects::quest_model::QuestModel;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardPageModel {
+    pub items: Vec<QuestModel>,
+    /// Pass back as `cursor` to fetch the next page, `None` on the last page.
+    pub next_cursor: Option<String>,
+    /// Number of quests matching the filter across all pages.
+    pub total: i64,
+}
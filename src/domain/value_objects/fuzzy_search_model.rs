@@ -0,0 +1,30 @@
+use serde::{ Deserialize, Serialize };
+
+use crate::domain::value_objects::quest_model::QuestModel;
+
+/// Lowest `word_similarity` between the search and a quest name that still counts as a match.
+pub const FUZZY_MATCH_THRESHOLD: f32 = 0.4;
+/// Lowest similarity for a word of a quest name to be offered as a correction.
+pub const SUGGESTION_THRESHOLD: f32 = 0.3;
+/// Longer searches are not worth correcting word by word.
+pub const MAX_SUGGESTION_WORDS: usize = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FuzzySearchFilter {
+    pub q: String,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FuzzyQuestHitModel {
+    pub quest: QuestModel,
+    /// `word_similarity` between the search and the quest name, from 0 to 1.
+    pub similarity: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FuzzySearchModel {
+    pub items: Vec<FuzzyQuestHitModel>,
+    /// A corrected search built from words of existing quest names, only when nothing matched.
+    pub did_you_mean: Option<String>,
+}
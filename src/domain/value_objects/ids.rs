@@ -0,0 +1,97 @@
+use std::{ fmt, num::ParseIntError, str::FromStr };
+
+use diesel::{
+    deserialize::{ self, FromSql, FromSqlRow },
+    expression::AsExpression,
+    pg::{ Pg, PgValue },
+    serialize::{ self, Output, ToSql },
+    sql_types::Integer,
+};
+use serde::{ Deserialize, Serialize };
+
+/// Declares an `i32` backed identifier that binds to `INTEGER` columns,
+/// (de)serializes as a bare number and parses from path segments and JWT subjects.
+macro_rules! id_newtype {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(
+            Debug,
+            Clone,
+            Copy,
+            PartialEq,
+            Eq,
+            Hash,
+            PartialOrd,
+            Ord,
+            Serialize,
+            Deserialize,
+            AsExpression,
+            FromSqlRow
+        )]
+        #[serde(transparent)]
+        #[diesel(sql_type = Integer)]
+        pub struct $name(pub i32);
+
+        impl ToSql<Integer, Pg> for $name {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+                <i32 as ToSql<Integer, Pg>>::to_sql(&self.0, out)
+            }
+        }
+
+        impl FromSql<Integer, Pg> for $name {
+            fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+                <i32 as FromSql<Integer, Pg>>::from_sql(bytes).map(Self)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseIntError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                s.parse::<i32>().map(Self)
+            }
+        }
+
+        impl From<$name> for i32 {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+    };
+}
+
+id_newtype!(
+    /// Primary key of `quests`.
+    QuestId
+);
+
+id_newtype!(
+    /// Primary key of `adventurers`.
+    AdventurerId
+);
+
+id_newtype!(
+    /// Primary key of `guild_commanders`.
+    GuildCommanderId
+);
+
+id_newtype!(
+    /// Primary key of `quest_applications`.
+    QuestApplicationId
+);
+
+id_newtype!(
+    /// Primary key of `quest_invitations`.
+    QuestInvitationId
+);
+
+id_newtype!(
+    /// Primary key of `notifications`.
+    NotificationId
+);
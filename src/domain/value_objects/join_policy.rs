@@ -0,0 +1,50 @@
+use std::{ io::Write, str::FromStr };
+
+use diesel::{
+    deserialize::{ self, FromSql, FromSqlRow },
+    expression::AsExpression,
+    pg::{ Pg, PgValue },
+    serialize::{ self, IsNull, Output, ToSql },
+    sql_types::Varchar,
+};
+use serde::{ Deserialize, Serialize };
+use strum_macros::{ Display, EnumString };
+
+/// How adventurers get into a quest's crew.
+#[derive(
+    Display,
+    EnumString,
+    Default,
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Hash,
+    AsExpression,
+    FromSqlRow
+)]
+#[diesel(sql_type = Varchar)]
+pub enum JoinPolicy {
+    /// Anyone meeting the requirements joins straight away.
+    #[default]
+    Open,
+    /// Adventurers apply and the commander approves or rejects each application.
+    Approval,
+}
+
+impl ToSql<Varchar, Pg> for JoinPolicy {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.to_string().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Varchar, Pg> for JoinPolicy {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let value = <String as FromSql<Varchar, Pg>>::from_sql(bytes)?;
+        JoinPolicy::from_str(&value).map_err(|_| format!("Unknown join policy: {}", value).into())
+    }
+}
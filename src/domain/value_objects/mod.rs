@@ -1,6 +1,21 @@
 pub mod adventurer_model;
+pub mod adventurer_rank;
 pub mod board_checking_filter;
+pub mod board_cursor;
+pub mod board_page_model;
+pub mod fuzzy_search_model;
 pub mod guild_commander_model;
+pub mod ids;
+pub mod join_policy;
+pub mod notification_model;
+pub mod progression;
+pub mod quest_actor;
 pub mod quest_adventurer_junction;
+pub mod quest_applications;
+pub mod quest_invitations;
 pub mod quest_model;
-pub mod quest_statuses;
\ No newline at end of file
+pub mod quest_status_event_model;
+pub mod quest_statuses;
+pub mod quest_transition_model;
+pub mod quest_waitlist;
+pub mod tag_model;
\ No newline at end of file
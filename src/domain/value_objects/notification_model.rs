@@ -0,0 +1,67 @@
+use std::{ io::Write, str::FromStr };
+
+use chrono::NaiveDateTime;
+use diesel::{
+    deserialize::{ self, FromSql, FromSqlRow },
+    expression::AsExpression,
+    pg::{ Pg, PgValue },
+    serialize::{ self, IsNull, Output, ToSql },
+    sql_types::Varchar,
+};
+use serde::{ Deserialize, Serialize };
+use strum_macros::{ Display, EnumString };
+
+use crate::domain::value_objects::ids::{ NotificationId, QuestId };
+
+/// Most notifications returned at once, newest first.
+pub const MAX_NOTIFICATIONS: i64 = 100;
+
+#[derive(
+    Display,
+    EnumString,
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Hash,
+    AsExpression,
+    FromSqlRow
+)]
+#[diesel(sql_type = Varchar)]
+pub enum NotificationKind {
+    /// A slot opened up and the adventurer was moved from the waitlist into the crew.
+    WaitlistPromoted,
+}
+
+impl ToSql<Varchar, Pg> for NotificationKind {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.to_string().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Varchar, Pg> for NotificationKind {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let value = <String as FromSql<Varchar, Pg>>::from_sql(bytes)?;
+        NotificationKind::from_str(&value).map_err(|_| format!("Unknown notification kind: {}", value).into())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationFilter {
+    #[serde(default)]
+    pub unread_only: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationModel {
+    pub id: NotificationId,
+    pub kind: NotificationKind,
+    pub quest_id: Option<QuestId>,
+    pub message: String,
+    pub read_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
@@ -0,0 +1,117 @@
+use serde::{ Deserialize, Serialize };
+
+use crate::domain::value_objects::adventurer_rank::AdventurerRank;
+
+/// Levels stop here even if the curve would allow more.
+pub const MAX_LEVEL: i32 = 100;
+
+/// Bounds on the per-level growth factor, flatter curves never level up faster
+/// and steeper ones put the higher levels out of reach anyway.
+pub const MIN_GROWTH: f64 = 1.0;
+pub const MAX_GROWTH: f64 = 2.0;
+
+/// Geometric level curve: going from level `n` to `n + 1` costs
+/// `base_xp * growth^(n - 1)` XP, rounded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelCurve {
+    pub base_xp: i64,
+    pub growth: f64,
+}
+
+/// How quest outcomes turn into adventurer progress.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProgressionRules {
+    pub level_curve: LevelCurve,
+    /// Share of a quest's rewards taken from each crew member when it fails, 0 disables it.
+    pub failure_penalty_percent: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdventurerProgressModel {
+    pub level: i32,
+    pub rank: AdventurerRank,
+    pub xp: i32,
+    pub gold: i32,
+    /// Total XP at which the current level was reached.
+    pub current_level_xp: i64,
+    /// Total XP needed for the next level, `None` at `MAX_LEVEL`.
+    pub next_level_xp: Option<i64>,
+    pub xp_to_next_level: Option<i64>,
+}
+
+impl LevelCurve {
+    /// Total XP needed to reach `level`, level 1 starts at 0.
+    pub fn total_xp_for_level(&self, level: i32) -> i64 {
+        (1..level.min(MAX_LEVEL)).fold(0, |total: i64, from| total.saturating_add(self.xp_to_advance(from)))
+    }
+
+    pub fn level_for_xp(&self, xp: i64) -> i32 {
+        let mut level = 1;
+        let mut required = 0;
+
+        while level < MAX_LEVEL {
+            required = self.xp_to_advance(level).saturating_add(required);
+
+            if xp < required {
+                break;
+            }
+
+            level += 1;
+        }
+
+        level
+    }
+
+    /// Saturates at `i64::MAX` on steep curves, callers must add it with `saturating_add`.
+    fn xp_to_advance(&self, from: i32) -> i64 {
+        ((self.base_xp as f64) * self.growth.powi(from - 1)).round().max(1.0) as i64
+    }
+}
+
+impl ProgressionRules {
+    /// Reports the stored `level`, the same one join gating and the board filter read,
+    /// so a curve change only shows up once the adventurer's XP next changes.
+    pub fn progress(&self, level: i32, xp: i32, gold: i32) -> AdventurerProgressModel {
+        let next_level_xp = (level < MAX_LEVEL).then(|| self.level_curve.total_xp_for_level(level + 1));
+
+        AdventurerProgressModel {
+            level,
+            rank: AdventurerRank::for_level(level),
+            xp,
+            gold,
+            current_level_xp: self.level_curve.total_xp_for_level(level),
+            next_level_xp,
+            xp_to_next_level: next_level_xp.map(|next| (next - i64::from(xp)).max(0)),
+        }
+    }
+
+    /// The share of `reward` lost on failure.
+    pub fn penalty(&self, reward: i32) -> i32 {
+        ((i64::from(reward) * i64::from(self.failure_penalty_percent)) / 100) as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steepest_allowed_curve_saturates_instead_of_overflowing() {
+        let curve = LevelCurve { base_xp: i64::MAX / 2, growth: MAX_GROWTH };
+
+        assert_eq!(curve.total_xp_for_level(MAX_LEVEL), i64::MAX);
+        assert_eq!(curve.level_for_xp(i64::from(i32::MAX)), 1);
+    }
+
+    #[test]
+    fn level_and_total_xp_agree() {
+        let curve = LevelCurve { base_xp: 100, growth: 1.5 };
+
+        for level in 1..=10 {
+            let reached_at = curve.total_xp_for_level(level);
+
+            assert_eq!(curve.level_for_xp(reached_at), level);
+            assert_eq!(curve.level_for_xp(reached_at - 1), (level - 1).max(1));
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use crate::domain::value_objects::ids::GuildCommanderId;
+
+/// Who moved a quest along its lifecycle, as recorded in `quest_status_events`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuestActor {
+    GuildCommander(GuildCommanderId),
+    /// Background jobs such as the deadline scheduler.
+    System,
+}
+
+impl QuestActor {
+    pub fn role(&self) -> &'static str {
+        match self {
+            QuestActor::GuildCommander(_) => "GuildCommander",
+            QuestActor::System => "System",
+        }
+    }
+
+    pub fn id(&self) -> Option<i32> {
+        match self {
+            QuestActor::GuildCommander(guild_commander_id) => Some((*guild_commander_id).into()),
+            QuestActor::System => None,
+        }
+    }
+}
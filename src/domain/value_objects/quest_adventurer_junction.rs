@@ -4,16 +4,103 @@ use serde::{Deserialize, Serialize};
 use crate::{
     domain::entities::adventures::AdventurerEntity,
     domain::entities::quests::QuestEntity,
+    domain::errors::{ AppError, AppResult },
+    domain::value_objects::{
+        adventurer_rank::AdventurerRank,
+        ids::{ AdventurerId, QuestId },
+        join_policy::JoinPolicy,
+    },
     infrastructure::postgres::schema::quest_adventurer_junction,
 };
 
-pub const MAX_ADVENTURERS_PER_QUEST: i64 = 5;
+pub const DEFAULT_MIN_CREW: i32 = 1;
+pub const DEFAULT_MAX_CREW: i32 = 5;
+/// Upper bound for `max_crew`, large enough for raids.
+pub const CREW_SIZE_LIMIT: i32 = 100;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Insertable, Queryable, Associations)]
 #[diesel(belongs_to(AdventurerEntity, foreign_key = adventurer_id))]
 #[diesel(belongs_to(QuestEntity, foreign_key = quest_id))]
 #[diesel(table_name = quest_adventurer_junction)]
 pub struct QuestAdventurerJunction {
-    pub adventurer_id: i32,
-    pub quest_id: i32
-}
\ No newline at end of file
+    pub adventurer_id: AdventurerId,
+    pub quest_id: QuestId
+}
+
+/// Checked while the quest row is locked, see `CrewSwitchboardRepository::join`.
+pub fn ensure_joinable(quest: &QuestEntity) -> AppResult<()> {
+    if !quest.status.is_crew_changeable() {
+        return Err(AppError::Conflict("Quest is not joinable".to_string()));
+    }
+
+    Ok(())
+}
+
+pub fn is_full(quest: &QuestEntity, crew_count: i64) -> bool {
+    crew_count >= i64::from(quest.max_crew)
+}
+
+/// Checked under the same lock as `ensure_joinable`. Full quests can still be
+/// queued for, see `CrewSwitchboardRepository::join`.
+pub fn ensure_has_room(quest: &QuestEntity, crew_count: i64) -> AppResult<()> {
+    if is_full(quest, crew_count) {
+        return Err(AppError::Conflict("Quest is full".to_string()));
+    }
+
+    Ok(())
+}
+
+/// Quests with the `Approval` policy are only joined through an approved application.
+pub fn ensure_open_to_join(quest: &QuestEntity) -> AppResult<()> {
+    if quest.join_policy == JoinPolicy::Approval {
+        return Err(AppError::Forbidden("Quest requires approval, apply to join it".to_string()));
+    }
+
+    Ok(())
+}
+
+/// The quest's level and rank requirements against the adventurer's current level.
+pub fn ensure_requirements_met(quest: &QuestEntity, adventurer_level: i32) -> AppResult<()> {
+    if adventurer_level < quest.min_level {
+        return Err(
+            AppError::Forbidden(
+                format!("Requires level {}, you are level {}", quest.min_level, adventurer_level)
+            )
+        );
+    }
+
+    if let Some(min_rank) = quest.min_rank {
+        let rank = AdventurerRank::for_level(adventurer_level);
+
+        if rank < min_rank {
+            return Err(AppError::Forbidden(format!("Requires {} rank, you are {}", min_rank, rank)));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checked while the quest row is locked, see `CrewSwitchboardRepository::leave`.
+pub fn ensure_leaveable(quest: &QuestEntity) -> AppResult<()> {
+    if !quest.status.is_crew_changeable() {
+        return Err(AppError::Conflict("Quest is not leaveable".to_string()));
+    }
+
+    Ok(())
+}
+
+pub fn validate_crew_size(min_crew: i32, max_crew: i32) -> AppResult<()> {
+    if min_crew < 1 {
+        return Err(AppError::Validation("min_crew must be at least 1".to_string()));
+    }
+
+    if max_crew < min_crew {
+        return Err(AppError::Validation("max_crew must not be less than min_crew".to_string()));
+    }
+
+    if max_crew > CREW_SIZE_LIMIT {
+        return Err(AppError::Validation(format!("max_crew must not exceed {}", CREW_SIZE_LIMIT)));
+    }
+
+    Ok(())
+}
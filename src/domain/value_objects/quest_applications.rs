@@ -0,0 +1,108 @@
+use std::{ io::Write, str::FromStr };
+
+use chrono::NaiveDateTime;
+use diesel::{
+    deserialize::{ self, FromSql, FromSqlRow },
+    expression::AsExpression,
+    pg::{ Pg, PgValue },
+    serialize::{ self, IsNull, Output, ToSql },
+    sql_types::Varchar,
+};
+use serde::{ Deserialize, Serialize };
+use strum_macros::{ Display, EnumString };
+
+use crate::domain::{
+    errors::{ AppError, AppResult },
+    value_objects::ids::{ AdventurerId, QuestApplicationId, QuestId },
+};
+
+pub const MAX_APPLICATION_MESSAGE_LENGTH: usize = 500;
+
+#[derive(
+    Display,
+    EnumString,
+    Default,
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Hash,
+    AsExpression,
+    FromSqlRow
+)]
+#[diesel(sql_type = Varchar)]
+pub enum ApplicationStatus {
+    /// Waiting for the commander, the only status an application can leave.
+    #[default]
+    Pending,
+    Approved,
+    Rejected,
+    Withdrawn,
+}
+
+impl ToSql<Varchar, Pg> for ApplicationStatus {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.to_string().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Varchar, Pg> for ApplicationStatus {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let value = <String as FromSql<Varchar, Pg>>::from_sql(bytes)?;
+        ApplicationStatus::from_str(&value).map_err(|_| format!("Unknown application status: {}", value).into())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplyQuestModel {
+    /// A short note to the commander, e.g. why the adventurer fits the quest.
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplicationFilter {
+    pub status: Option<ApplicationStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuestApplicationModel {
+    pub id: QuestApplicationId,
+    pub quest_id: QuestId,
+    pub adventurer_id: AdventurerId,
+    pub adventurer_username: String,
+    pub adventurer_level: i32,
+    pub message: Option<String>,
+    pub status: ApplicationStatus,
+    pub decided_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+/// Trims the message, a blank one is dropped.
+pub fn normalize_message(message: Option<&str>) -> AppResult<Option<String>> {
+    let Some(message) = message.map(str::trim).filter(|message| !message.is_empty()) else {
+        return Ok(None);
+    };
+
+    if message.chars().count() > MAX_APPLICATION_MESSAGE_LENGTH {
+        return Err(
+            AppError::Validation(
+                format!("message must be at most {} characters", MAX_APPLICATION_MESSAGE_LENGTH)
+            )
+        );
+    }
+
+    Ok(Some(message.to_string()))
+}
+
+/// Decisions are final, only pending applications can be approved, rejected or withdrawn.
+pub fn ensure_pending(status: ApplicationStatus) -> AppResult<()> {
+    if status != ApplicationStatus::Pending {
+        return Err(AppError::Conflict(format!("Application is already {}", status)));
+    }
+
+    Ok(())
+}
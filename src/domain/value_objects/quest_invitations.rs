@@ -0,0 +1,86 @@
+use std::{ io::Write, str::FromStr };
+
+use chrono::NaiveDateTime;
+use diesel::{
+    deserialize::{ self, FromSql, FromSqlRow },
+    expression::AsExpression,
+    pg::{ Pg, PgValue },
+    serialize::{ self, IsNull, Output, ToSql },
+    sql_types::Varchar,
+};
+use serde::{ Deserialize, Serialize };
+use strum_macros::{ Display, EnumString };
+
+use crate::domain::{
+    errors::{ AppError, AppResult },
+    value_objects::ids::{ GuildCommanderId, QuestId, QuestInvitationId },
+};
+
+#[derive(
+    Display,
+    EnumString,
+    Default,
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Hash,
+    AsExpression,
+    FromSqlRow
+)]
+#[diesel(sql_type = Varchar)]
+pub enum InvitationStatus {
+    /// Waiting for the adventurer until `expires_at`.
+    #[default]
+    Pending,
+    Accepted,
+    Declined,
+    /// Left pending past `expires_at`, recorded when the commander invites again.
+    Expired,
+}
+
+impl ToSql<Varchar, Pg> for InvitationStatus {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.to_string().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Varchar, Pg> for InvitationStatus {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let value = <String as FromSql<Varchar, Pg>>::from_sql(bytes)?;
+        InvitationStatus::from_str(&value).map_err(|_| format!("Unknown invitation status: {}", value).into())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InviteAdventurerModel {
+    pub username: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuestInvitationModel {
+    pub id: QuestInvitationId,
+    pub quest_id: QuestId,
+    pub quest_name: String,
+    pub guild_commander_id: GuildCommanderId,
+    pub status: InvitationStatus,
+    pub expires_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+/// Only pending invitations that have not expired can be accepted or declined.
+pub fn ensure_answerable(status: InvitationStatus, expires_at: NaiveDateTime, now: NaiveDateTime) -> AppResult<()> {
+    if status != InvitationStatus::Pending {
+        return Err(AppError::Conflict(format!("Invitation is already {}", status)));
+    }
+
+    if expires_at <= now {
+        return Err(AppError::Conflict("Invitation has expired".to_string()));
+    }
+
+    Ok(())
+}
@@ -1,39 +1,108 @@
 use chrono::NaiveDateTime;
-use serde::{ Deserialize, Serialize };
+use serde::{ Deserialize, Deserializer, Serialize };
 
 use crate::{
     domain::{
         entities::quests::{ AddQuestEntity, EditQuestEntity },
-        value_objects::quest_statuses::QuestStatus,
+        errors::{ AppError, AppResult },
+        value_objects::{
+            adventurer_rank::AdventurerRank,
+            ids::{ GuildCommanderId, QuestId },
+            join_policy::JoinPolicy,
+            progression::MAX_LEVEL,
+            quest_adventurer_junction::{ DEFAULT_MAX_CREW, DEFAULT_MIN_CREW },
+            quest_statuses::QuestStatus,
+        },
     },
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QuestModel {
-    pub id: i32,
+    pub id: QuestId,
     pub name: String,
     pub description: Option<String>,
-    pub status: String,
-    pub guild_commander_id: i32,
+    pub status: QuestStatus,
+    pub guild_commander_id: GuildCommanderId,
+    pub min_crew: i32,
+    pub max_crew: i32,
+    pub starts_at: Option<NaiveDateTime>,
+    pub due_at: Option<NaiveDateTime>,
+    pub reward_xp: i32,
+    pub reward_gold: i32,
+    pub min_level: i32,
+    pub min_rank: Option<AdventurerRank>,
+    /// Bumped on every change to the quest or its crew, served as the `ETag`.
+    pub version: i32,
+    pub join_policy: JoinPolicy,
     pub adventurer_count: i64,
+    pub tags: Vec<String>,
+    /// Rank and highlighted snippets, only on board results searched with `q`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search: Option<QuestSearchHitModel>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuestSearchHitModel {
+    pub rank: f32,
+    /// The name with matched words wrapped in `<mark>`.
+    pub name_highlight: String,
+    /// Fragments of the description around the matches.
+    pub description_highlight: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedQuestModel {
+    #[serde(flatten)]
+    pub quest: QuestModel,
+    pub deleted_at: NaiveDateTime,
+    /// After this the quest is deleted for good and can no longer be restored.
+    pub purge_at: NaiveDateTime,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AddQuestModel {
     pub name: String,
     pub description: Option<String>,
-    pub guild_commander_id: i32,
+    pub min_crew: Option<i32>,
+    pub max_crew: Option<i32>,
+    pub starts_at: Option<NaiveDateTime>,
+    /// Quests still InJourney after this are failed, Open ones are cancelled.
+    pub due_at: Option<NaiveDateTime>,
+    /// Credited to every crew member when the quest is completed.
+    pub reward_xp: Option<i32>,
+    pub reward_gold: Option<i32>,
+    /// Adventurers below this level cannot join.
+    pub min_level: Option<i32>,
+    /// Adventurers below this rank cannot join.
+    pub min_rank: Option<AdventurerRank>,
+    /// `Approval` makes adventurers apply instead of joining straight away.
+    pub join_policy: Option<JoinPolicy>,
+    /// Categories such as escort, hunt or gather as well as free-form tags.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Keeps the quest off the board until it is published.
+    #[serde(default)]
+    pub draft: bool,
 }
 
 impl AddQuestModel {
-    pub fn to_entity(&self, guild_commander_id: i32) -> AddQuestEntity {
+    pub fn to_entity(&self, guild_commander_id: GuildCommanderId) -> AddQuestEntity {
         AddQuestEntity {
             name: self.name.clone(),
             description: self.description.clone(),
             guild_commander_id,
-            status: QuestStatus::Open.to_string(),
+            status: if self.draft { QuestStatus::Draft } else { QuestStatus::Open },
+            min_crew: self.min_crew.unwrap_or(DEFAULT_MIN_CREW),
+            max_crew: self.max_crew.unwrap_or(DEFAULT_MAX_CREW),
+            starts_at: self.starts_at,
+            due_at: self.due_at,
+            reward_xp: self.reward_xp.unwrap_or(0),
+            reward_gold: self.reward_gold.unwrap_or(0),
+            min_level: self.min_level.unwrap_or(1),
+            min_rank: self.min_rank,
+            join_policy: self.join_policy.unwrap_or_default(),
             created_at: chrono::Utc::now().naive_utc(),
             updated_at: chrono::Utc::now().naive_utc(),
         }
@@ -44,16 +113,80 @@ impl AddQuestModel {
 pub struct EditQuestModel {
     pub name: Option<String>,
     pub description: Option<String>,
-    pub guild_commander_id: i32,
+    pub min_crew: Option<i32>,
+    pub max_crew: Option<i32>,
+    pub reward_xp: Option<i32>,
+    pub reward_gold: Option<i32>,
+    pub min_level: Option<i32>,
+    /// `null` clears the rank requirement, leaving it out keeps it.
+    #[serde(default, deserialize_with = "double_option")]
+    pub min_rank: Option<Option<AdventurerRank>>,
+    pub join_policy: Option<JoinPolicy>,
+    /// Replaces every tag of the quest when given.
+    pub tags: Option<Vec<String>>,
 }
 
 impl EditQuestModel {
-    pub fn to_entity(&self, guild_commander_id: i32) -> EditQuestEntity {
+    pub fn to_entity(&self) -> EditQuestEntity {
         EditQuestEntity {
             name: self.name.clone(),
             description: self.description.clone(),
-            guild_commander_id,
+            min_crew: self.min_crew,
+            max_crew: self.max_crew,
+            reward_xp: self.reward_xp,
+            reward_gold: self.reward_gold,
+            min_level: self.min_level,
+            min_rank: self.min_rank,
+            join_policy: self.join_policy,
             updated_at: chrono::Utc::now().naive_utc(),
         }
     }
 }
+
+/// Tells a field sent as `null` (`Some(None)`) apart from one left out (`None`).
+fn double_option<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de>
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+pub fn validate_schedule(
+    starts_at: Option<NaiveDateTime>,
+    due_at: Option<NaiveDateTime>,
+    now: NaiveDateTime
+) -> AppResult<()> {
+    // The deadline sweep cancels Open quests whose start has passed, so it cannot be in the past.
+    if starts_at.is_some_and(|starts_at| starts_at <= now) {
+        return Err(AppError::Validation("starts_at must be in the future".to_string()));
+    }
+
+    if let Some(due_at) = due_at {
+        if due_at <= now {
+            return Err(AppError::Validation("due_at must be in the future".to_string()));
+        }
+
+        if starts_at.is_some_and(|starts_at| due_at <= starts_at) {
+            return Err(AppError::Validation("due_at must be after starts_at".to_string()));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn validate_rewards(reward_xp: i32, reward_gold: i32) -> AppResult<()> {
+    if reward_xp < 0 || reward_gold < 0 {
+        return Err(AppError::Validation("Rewards must not be negative".to_string()));
+    }
+
+    Ok(())
+}
+
+pub fn validate_min_level(min_level: i32) -> AppResult<()> {
+    if !(1..=MAX_LEVEL).contains(&min_level) {
+        return Err(AppError::Validation(format!("min_level must be between 1 and {}", MAX_LEVEL)));
+    }
+
+    Ok(())
+}
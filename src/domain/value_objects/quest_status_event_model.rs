@@ -0,0 +1,14 @@
+use chrono::NaiveDateTime;
+use serde::{ Deserialize, Serialize };
+
+use crate::domain::value_objects::quest_statuses::QuestStatus;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuestStatusEventModel {
+    pub from_status: QuestStatus,
+    pub to_status: QuestStatus,
+    pub actor_role: String,
+    pub actor_id: Option<i32>,
+    pub reason: Option<String>,
+    pub created_at: NaiveDateTime,
+}
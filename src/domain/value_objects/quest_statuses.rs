@@ -1,8 +1,37 @@
+use std::{ io::Write, str::FromStr };
+
+use diesel::{
+    deserialize::{ self, FromSql, FromSqlRow },
+    expression::AsExpression,
+    pg::{ Pg, PgValue },
+    serialize::{ self, IsNull, Output, ToSql },
+    sql_types::Varchar,
+};
 use serde::{ Deserialize, Serialize };
-use strum_macros::Display;
+use strum_macros::{ Display, EnumString };
+
+use crate::domain::{ entities::quests::QuestEntity, errors::{ AppError, AppResult } };
 
-#[derive(Display, Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(
+    Display,
+    EnumString,
+    Default,
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Hash,
+    AsExpression,
+    FromSqlRow
+)]
+#[diesel(sql_type = Varchar)]
 pub enum QuestStatus {
+    #[strum(serialize = "Draft")]
+    Draft,
+
     #[default]
     #[strum(serialize = "Open")]
     Open,
@@ -15,4 +44,301 @@ pub enum QuestStatus {
 
     #[strum(serialize = "Failed")]
     Failed,
+
+    #[strum(serialize = "Cancelled")]
+    Cancelled,
+
+    #[strum(serialize = "Archived")]
+    Archived,
+}
+
+impl ToSql<Varchar, Pg> for QuestStatus {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.to_string().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Varchar, Pg> for QuestStatus {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let value = <String as FromSql<Varchar, Pg>>::from_sql(bytes)?;
+        QuestStatus::from_str(&value).map_err(|_| format!("Unknown quest status: {}", value).into())
+    }
+}
+
+/// Statuses in which adventurers may join or leave the crew.
+pub const CREW_CHANGEABLE_STATUSES: [QuestStatus; 2] = [QuestStatus::Open, QuestStatus::Failed];
+
+/// Statuses in which the commander may still edit or remove the quest.
+pub const EDITABLE_STATUSES: [QuestStatus; 2] = [QuestStatus::Draft, QuestStatus::Open];
+
+/// Condition that must hold for a transition to be taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionGuard {
+    /// The crew has reached the quest's `min_crew`.
+    MinCrewMet,
+    /// Nobody has joined yet.
+    NoCrew,
+    /// The crew does not exceed the quest's `max_crew`.
+    CrewWithinCapacity,
+}
+
+/// Work the repository performs in the same transaction as the status change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionEffect {
+    /// Removes every adventurer from the crew.
+    ReleaseCrew,
+    /// Credits the quest's XP and gold rewards to every crew member.
+    RewardCrew,
+    /// Takes the configured failure penalty from every crew member.
+    PenalizeCrew,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuestTransition {
+    pub from: QuestStatus,
+    pub to: QuestStatus,
+    pub guards: &'static [TransitionGuard],
+    pub effects: &'static [TransitionEffect],
+}
+
+/// Facts about a quest that guards are evaluated against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransitionContext {
+    pub crew_count: i64,
+    pub min_crew: i32,
+    pub max_crew: i32,
+}
+
+impl TransitionContext {
+    pub fn for_quest(quest: &QuestEntity, crew_count: i64) -> Self {
+        Self {
+            crew_count,
+            min_crew: quest.min_crew,
+            max_crew: quest.max_crew,
+        }
+    }
+}
+
+/// Every allowed edge of the quest lifecycle. Anything not listed here is rejected.
+pub const QUEST_TRANSITIONS: &[QuestTransition] = &[
+    QuestTransition {
+        from: QuestStatus::Draft,
+        to: QuestStatus::Open,
+        guards: &[],
+        effects: &[],
+    },
+    QuestTransition {
+        from: QuestStatus::Draft,
+        to: QuestStatus::Cancelled,
+        guards: &[],
+        effects: &[],
+    },
+    QuestTransition {
+        from: QuestStatus::Open,
+        to: QuestStatus::Draft,
+        guards: &[TransitionGuard::NoCrew],
+        effects: &[],
+    },
+    QuestTransition {
+        from: QuestStatus::Open,
+        to: QuestStatus::InJourney,
+        guards: &[TransitionGuard::MinCrewMet, TransitionGuard::CrewWithinCapacity],
+        effects: &[],
+    },
+    QuestTransition {
+        from: QuestStatus::Open,
+        to: QuestStatus::Cancelled,
+        guards: &[],
+        effects: &[TransitionEffect::ReleaseCrew],
+    },
+    QuestTransition {
+        from: QuestStatus::InJourney,
+        to: QuestStatus::Completed,
+        guards: &[],
+        effects: &[TransitionEffect::RewardCrew],
+    },
+    QuestTransition {
+        from: QuestStatus::InJourney,
+        to: QuestStatus::Failed,
+        guards: &[],
+        effects: &[TransitionEffect::PenalizeCrew],
+    },
+    QuestTransition {
+        from: QuestStatus::Failed,
+        to: QuestStatus::InJourney,
+        guards: &[TransitionGuard::MinCrewMet, TransitionGuard::CrewWithinCapacity],
+        effects: &[],
+    },
+    QuestTransition {
+        from: QuestStatus::Failed,
+        to: QuestStatus::Cancelled,
+        guards: &[],
+        effects: &[TransitionEffect::ReleaseCrew],
+    },
+    QuestTransition {
+        from: QuestStatus::Failed,
+        to: QuestStatus::Archived,
+        guards: &[],
+        effects: &[],
+    },
+    QuestTransition {
+        from: QuestStatus::Completed,
+        to: QuestStatus::Archived,
+        guards: &[],
+        effects: &[],
+    },
+    QuestTransition {
+        from: QuestStatus::Cancelled,
+        to: QuestStatus::Archived,
+        guards: &[],
+        effects: &[],
+    },
+];
+
+impl TransitionGuard {
+    pub fn check(&self, context: &TransitionContext) -> AppResult<()> {
+        let (passed, reason) = match self {
+            TransitionGuard::MinCrewMet =>
+                (context.crew_count >= i64::from(context.min_crew), "the crew is below its minimum size"),
+            TransitionGuard::NoCrew => (context.crew_count == 0, "adventurers have already joined"),
+            TransitionGuard::CrewWithinCapacity =>
+                (context.crew_count <= i64::from(context.max_crew), "the crew is over capacity"),
+        };
+
+        if !passed {
+            return Err(AppError::InvalidTransition(reason.to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+impl QuestTransition {
+    /// The edge `from -> to` of `QUEST_TRANSITIONS`, if there is one.
+    pub fn find(from: QuestStatus, to: QuestStatus) -> Option<QuestTransition> {
+        QUEST_TRANSITIONS.iter().find(|transition| transition.from == from && transition.to == to).copied()
+    }
+
+    pub fn check_guards(&self, context: &TransitionContext) -> AppResult<()> {
+        for guard in self.guards {
+            guard.check(context).map_err(|err|
+                AppError::InvalidTransition(
+                    format!("Cannot move quest from {} to {}: {}", self.from, self.to, err)
+                )
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl QuestStatus {
+    pub fn is_crew_changeable(&self) -> bool {
+        CREW_CHANGEABLE_STATUSES.contains(self)
+    }
+
+    pub fn is_editable(&self) -> bool {
+        EDITABLE_STATUSES.contains(self)
+    }
+
+    /// Looks up the edge `self -> to` and checks its guards.
+    pub fn transition_to(
+        &self,
+        to: QuestStatus,
+        context: &TransitionContext
+    ) -> AppResult<QuestTransition> {
+        let transition = QuestTransition::find(*self, to)
+            .ok_or_else(||
+                AppError::InvalidTransition(format!("Cannot move quest from {} to {}", self, to))
+            )?;
+
+        transition.check_guards(context)?;
+
+        Ok(transition)
+    }
+
+    /// Statuses reachable from `self` whose guards currently pass.
+    pub fn allowed_next(&self, context: &TransitionContext) -> Vec<QuestStatus> {
+        QUEST_TRANSITIONS.iter()
+            .filter(|transition| transition.from == *self)
+            .filter(|transition| transition.guards.iter().all(|guard| guard.check(context).is_ok()))
+            .map(|transition| transition.to)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(crew_count: i64) -> TransitionContext {
+        TransitionContext { crew_count, min_crew: 2, max_crew: 4 }
+    }
+
+    #[test]
+    fn every_edge_is_listed_once() {
+        for (i, transition) in QUEST_TRANSITIONS.iter().enumerate() {
+            let duplicates = QUEST_TRANSITIONS[i + 1..]
+                .iter()
+                .filter(|other| other.from == transition.from && other.to == transition.to)
+                .count();
+
+            assert_eq!(duplicates, 0, "{} -> {} is listed twice", transition.from, transition.to);
+        }
+    }
+
+    #[test]
+    fn unlisted_edges_are_rejected() {
+        let result = QuestStatus::Completed.transition_to(QuestStatus::Open, &context(2));
+
+        assert!(matches!(result, Err(AppError::InvalidTransition(_))));
+        assert!(QuestStatus::Archived.allowed_next(&context(2)).is_empty());
+    }
+
+    #[test]
+    fn starting_a_journey_needs_the_minimum_crew() {
+        let result = QuestStatus::Open.transition_to(QuestStatus::InJourney, &context(1));
+
+        assert!(matches!(result, Err(AppError::InvalidTransition(_))));
+        assert!(QuestStatus::Open.transition_to(QuestStatus::InJourney, &context(2)).is_ok());
+    }
+
+    #[test]
+    fn starting_a_journey_rejects_an_overfull_crew() {
+        let result = QuestStatus::Failed.transition_to(QuestStatus::InJourney, &context(5));
+
+        assert!(matches!(result, Err(AppError::InvalidTransition(_))));
+        assert!(QuestStatus::Failed.transition_to(QuestStatus::InJourney, &context(4)).is_ok());
+    }
+
+    #[test]
+    fn returning_to_draft_needs_an_empty_crew() {
+        assert!(QuestStatus::Open.transition_to(QuestStatus::Draft, &context(1)).is_err());
+        assert!(QuestStatus::Open.transition_to(QuestStatus::Draft, &context(0)).is_ok());
+    }
+
+    #[test]
+    fn allowed_next_leaves_out_edges_whose_guards_fail() {
+        assert_eq!(
+            QuestStatus::Open.allowed_next(&context(0)),
+            vec![QuestStatus::Draft, QuestStatus::Cancelled]
+        );
+        assert_eq!(
+            QuestStatus::Open.allowed_next(&context(3)),
+            vec![QuestStatus::InJourney, QuestStatus::Cancelled]
+        );
+    }
+
+    #[test]
+    fn effects_follow_the_outcome() {
+        let effects = |from, to| QuestStatus::transition_to(&from, to, &context(2)).unwrap().effects;
+
+        assert_eq!(effects(QuestStatus::InJourney, QuestStatus::Completed), &[TransitionEffect::RewardCrew]);
+        assert_eq!(effects(QuestStatus::InJourney, QuestStatus::Failed), &[TransitionEffect::PenalizeCrew]);
+
+        assert_eq!(effects(QuestStatus::Open, QuestStatus::Cancelled), &[TransitionEffect::ReleaseCrew]);
+        assert_eq!(effects(QuestStatus::Failed, QuestStatus::Cancelled), &[TransitionEffect::ReleaseCrew]);
+        assert!(effects(QuestStatus::Open, QuestStatus::InJourney).is_empty());
+    }
 }
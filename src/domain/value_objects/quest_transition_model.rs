@@ -0,0 +1,16 @@
+use serde::{ Deserialize, Serialize };
+
+use crate::domain::value_objects::{ ids::QuestId, quest_statuses::QuestStatus };
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitionQuestModel {
+    pub to: QuestStatus,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuestTransitionsModel {
+    pub quest_id: QuestId,
+    pub status: QuestStatus,
+    pub allowed_next: Vec<QuestStatus>,
+}
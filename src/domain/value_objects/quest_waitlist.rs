@@ -0,0 +1,16 @@
+use serde::{ Deserialize, Serialize };
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JoinOptions {
+    /// Queue for the next free slot instead of failing when the quest is full.
+    #[serde(default)]
+    pub waitlist: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum JoinOutcome {
+    Joined,
+    /// `position` 1 is promoted first.
+    Waitlisted { position: i64 },
+}
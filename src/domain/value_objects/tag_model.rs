@@ -0,0 +1,96 @@
+use serde::{ Deserialize, Serialize };
+
+use crate::domain::errors::{ AppError, AppResult };
+
+pub const MAX_TAGS_PER_QUEST: usize = 10;
+pub const MAX_TAG_LENGTH: usize = 64;
+
+/// How the board combines the requested tags.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TagMatch {
+    /// Quests carrying at least one of the tags.
+    #[default]
+    Any,
+    /// Quests carrying every one of the tags.
+    All,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagUsageModel {
+    pub name: String,
+    /// Board visible quests carrying the tag.
+    pub quest_count: i64,
+}
+
+/// Tags are matched case-insensitively, so they are stored trimmed and lowercase.
+pub fn normalize_tag(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
+
+/// Normalizes and de-duplicates the tags given for a quest, keeping their order.
+pub fn normalize_tags(tags: &[String]) -> AppResult<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+
+    for tag in tags.iter().map(|tag| normalize_tag(tag)) {
+        if tag.is_empty() {
+            return Err(AppError::Validation("Tags must not be empty".to_string()));
+        }
+
+        if tag.chars().count() > MAX_TAG_LENGTH {
+            return Err(AppError::Validation(format!("Tags must not exceed {} characters", MAX_TAG_LENGTH)));
+        }
+
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+
+    if normalized.len() > MAX_TAGS_PER_QUEST {
+        return Err(AppError::Validation(format!("A quest can have at most {} tags", MAX_TAGS_PER_QUEST)));
+    }
+
+    Ok(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|tag| tag.to_string()).collect()
+    }
+
+    #[test]
+    fn trims_lowercases_and_dedups_keeping_order() {
+        let normalized = normalize_tags(&tags(&["  Hunt ", "escort", "HUNT", "Escort", "gather"])).unwrap();
+
+        assert_eq!(normalized, tags(&["hunt", "escort", "gather"]));
+    }
+
+    #[test]
+    fn rejects_blank_tags() {
+        assert!(matches!(normalize_tags(&tags(&["hunt", "   "])), Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn limits_tag_length_in_characters() {
+        let longest = "é".repeat(MAX_TAG_LENGTH);
+        let too_long = "é".repeat(MAX_TAG_LENGTH + 1);
+
+        assert!(normalize_tags(&[longest]).is_ok());
+        assert!(matches!(normalize_tags(&[too_long]), Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn limits_tags_per_quest_after_dedup() {
+        let mut distinct = (0..MAX_TAGS_PER_QUEST).map(|i| format!("tag{}", i)).collect::<Vec<_>>();
+        distinct.push("TAG0".to_string());
+
+        assert_eq!(normalize_tags(&distinct).unwrap().len(), MAX_TAGS_PER_QUEST);
+
+        distinct.push("one-too-many".to_string());
+
+        assert!(matches!(normalize_tags(&distinct), Err(AppError::Validation(_))));
+    }
+}
@@ -0,0 +1,260 @@
+use std::str::FromStr;
+
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{ HeaderMap, HeaderValue, header, request::Parts },
+    response::{ IntoResponse, Response },
+};
+use axum_extra::extract::cookie::CookieJar;
+
+use crate::{
+    config::{ config_loader, token_source::TokenSource },
+    domain::{ errors::{ AppError, AppResult }, value_objects::ids::{ AdventurerId, GuildCommanderId } },
+    infrastructure::jwt_authentication::{ self, jwt_model::{ Claims, Roles } },
+};
+
+const REALM: &str = "quests-tracker";
+
+/// Quest versions start at 1, so this one never matches.
+const NEVER_ISSUED_VERSION: i32 = 0;
+
+/// The adventurer behind a verified access token.
+///
+/// Taking this as a handler argument is what protects a route, there is no
+/// separate middleware to forget or to mix up with the commander one.
+#[derive(Debug, Clone, Copy)]
+pub struct CurrentAdventurer(pub AdventurerId);
+
+/// The guild commander behind a verified access token.
+#[derive(Debug, Clone, Copy)]
+pub struct CurrentGuildCommander(pub GuildCommanderId);
+
+/// The quest version a client's `If-Match` header asks for, `None` for `If-Match: *`.
+///
+/// Required, a missing header is rejected with 428 so that clients cannot skip
+/// the check by accident.
+#[derive(Debug, Clone, Copy)]
+pub struct IfMatch(pub Option<i32>);
+
+/// Strong entity tag of a quest version, as served in `ETag`.
+pub fn quest_etag(version: i32) -> String {
+    format!("\"{}\"", version)
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for CurrentAdventurer where S: Send + Sync {
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let config = config_loader::load().map_err(|err| AuthRejection::new(AppError::Internal(err), None))?;
+
+        let claims = authenticate(
+            &parts.headers,
+            &config.authentication.token_precedence,
+            config.adventurers_secret.secret
+        )?;
+
+        Ok(Self(principal_id(&claims, Roles::Adventurer)?))
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for CurrentGuildCommander where S: Send + Sync {
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let config = config_loader::load().map_err(|err| AuthRejection::new(AppError::Internal(err), None))?;
+
+        let claims = authenticate(
+            &parts.headers,
+            &config.authentication.token_precedence,
+            config.guild_commanders_secret.secret
+        )?;
+
+        Ok(Self(principal_id(&claims, Roles::GuildCommander)?))
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for IfMatch where S: Send + Sync {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let value = parts.headers
+            .get(header::IF_MATCH)
+            .ok_or_else(||
+                AppError::PreconditionRequired("If-Match header with the quest's ETag is required".to_string())
+            )?
+            .to_str()
+            .map_err(|_| AppError::Validation("If-Match header is invalid".to_string()))?
+            .trim();
+
+        parse_if_match(value).map(Self)
+    }
+}
+
+/// Reads a single entity tag or `*`. Weak tags and strong ones that were never
+/// issued for a quest are well formed, they just never match, so they map to a
+/// version no quest has and fail like any other stale tag. Anything else,
+/// including a list of tags, is rejected as invalid.
+fn parse_if_match(value: &str) -> AppResult<Option<i32>> {
+    if value == "*" {
+        return Ok(None);
+    }
+
+    let invalid = || AppError::Validation("If-Match must be a single entity tag or *".to_string());
+
+    let (weak, tag) = match value.strip_prefix("W/") {
+        Some(tag) => (true, tag),
+        None => (false, value),
+    };
+
+    let opaque = tag
+        .strip_prefix('"')
+        .and_then(|tag| tag.strip_suffix('"'))
+        .filter(|opaque| !opaque.contains('"'))
+        .ok_or_else(invalid)?;
+
+    if weak {
+        return Ok(Some(NEVER_ISSUED_VERSION));
+    }
+
+    Ok(Some(opaque.parse::<i32>().unwrap_or(NEVER_ISSUED_VERSION)))
+}
+
+fn principal_id<T>(claims: &Claims, role: Roles) -> Result<T, AuthRejection> where T: FromStr {
+    if claims.role != role {
+        return Err(
+            AuthRejection::new(
+                AppError::Forbidden(format!("This route requires the {} role", role)),
+                Some("insufficient_scope")
+            )
+        );
+    }
+
+    claims.sub.parse::<T>().map_err(|_| {
+        AuthRejection::new(
+            AppError::Unauthorized("Access token subject is invalid".to_string()),
+            Some("invalid_token")
+        )
+    })
+}
+
+/// Authentication failure rendered as the usual error envelope plus a
+/// `WWW-Authenticate` challenge.
+#[derive(Debug)]
+pub struct AuthRejection {
+    error: AppError,
+    error_code: Option<&'static str>,
+}
+
+impl AuthRejection {
+    fn new(error: AppError, error_code: Option<&'static str>) -> Self {
+        Self { error, error_code }
+    }
+}
+
+impl IntoResponse for AuthRejection {
+    fn into_response(self) -> Response {
+        let value = match self.error_code {
+            Some(code) => format!("Bearer realm=\"{REALM}\", error=\"{code}\""),
+            None => format!("Bearer realm=\"{REALM}\""),
+        };
+
+        let mut response = self.error.into_response();
+        response.headers_mut().insert(header::WWW_AUTHENTICATE, HeaderValue::from_str(&value).unwrap());
+        response
+    }
+}
+
+/// Finds the access token following `precedence` and verifies it against `secret`.
+pub fn authenticate(
+    headers: &HeaderMap,
+    precedence: &[TokenSource],
+    secret: String
+) -> Result<Claims, AuthRejection> {
+    let token = find_token(headers, precedence)?;
+
+    jwt_authentication::verify_token(secret, token).map_err(|_| {
+        AuthRejection::new(
+            AppError::Unauthorized("Access token is invalid or expired".to_string()),
+            Some("invalid_token")
+        )
+    })
+}
+
+fn find_token(headers: &HeaderMap, precedence: &[TokenSource]) -> Result<String, AuthRejection> {
+    for source in precedence {
+        let token = match source {
+            TokenSource::Header => bearer_token(headers)?,
+            TokenSource::Cookie => CookieJar::from_headers(headers)
+                .get("act")
+                .map(|cookie| cookie.value().to_string()),
+        };
+
+        if let Some(token) = token {
+            return Ok(token);
+        }
+    }
+
+    Err(AuthRejection::new(AppError::Unauthorized("Missing access token".to_string()), None))
+}
+
+/// `Ok(None)` when there is no `Authorization` header at all, an error when
+/// the header is present but not a well formed bearer credential.
+fn bearer_token(headers: &HeaderMap) -> Result<Option<String>, AuthRejection> {
+    let Some(value) = headers.get(header::AUTHORIZATION) else {
+        return Ok(None);
+    };
+
+    let token = value
+        .to_str()
+        .ok()
+        .and_then(|value| value.split_once(' '))
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("Bearer"))
+        .map(|(_, token)| token.trim())
+        .filter(|token| !token.is_empty());
+
+    match token {
+        Some(token) => Ok(Some(token.to_string())),
+        None =>
+            Err(
+                AuthRejection::new(
+                    AppError::Validation(
+                        "Authorization header must be in the form 'Bearer <token>'".to_string()
+                    ),
+                    Some("invalid_request")
+                )
+            ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn star_matches_any_version() {
+        assert_eq!(parse_if_match("*").unwrap(), None);
+    }
+
+    #[test]
+    fn reads_the_version_of_a_quest_etag() {
+        assert_eq!(parse_if_match(&quest_etag(7)).unwrap(), Some(7));
+    }
+
+    #[test]
+    fn weak_and_foreign_tags_never_match() {
+        for value in ["W/\"7\"", "\"abc\"", "\"\"", "\"99999999999\""] {
+            assert_eq!(parse_if_match(value).unwrap(), Some(NEVER_ISSUED_VERSION), "{}", value);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_values() {
+        for value in ["7", "\"7", "7\"", "W/7", "\"1\", \"2\"", "\"a\"b\"", "**", ""] {
+            assert!(matches!(parse_if_match(value), Err(AppError::Validation(_))), "accepted {}", value);
+        }
+    }
+}
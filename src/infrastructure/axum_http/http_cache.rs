@@ -0,0 +1,97 @@
+use std::time::SystemTime;
+
+use axum::{ Json, http::{ HeaderMap, StatusCode }, response::{ IntoResponse, Response } };
+use axum_extra::headers::{ CacheControl, ETag, HeaderMapExt, IfModifiedSince, IfNoneMatch, LastModified };
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use sha2::{ Digest, Sha256 };
+
+use crate::{
+    domain::value_objects::{ board_page_model::BoardPageModel, quest_model::QuestModel },
+    infrastructure::axum_http::extractors::quest_etag,
+};
+
+/// What a client's cached copy is checked against, see `conditional_json`.
+pub struct Validators {
+    pub etag: ETag,
+    pub last_modified: Option<NaiveDateTime>,
+}
+
+impl Validators {
+    /// The strong `ETag` that `If-Match` on quest edits is checked against.
+    pub fn for_quest(quest: &QuestModel) -> Self {
+        Self {
+            etag: quest_etag(quest.version).parse().unwrap(),
+            last_modified: Some(quest.updated_at),
+        }
+    }
+
+    /// A weak `ETag` over which quests are on the page, when each was last
+    /// updated and how big its crew is. There is no `Last-Modified`: the newest
+    /// `updated_at` on the page cannot see quests that left it, so pages are
+    /// revalidated by `ETag` only and `If-Modified-Since` is ignored.
+    pub fn for_board(page: &BoardPageModel) -> Self {
+        let mut hasher = Sha256::new();
+
+        for quest in &page.items {
+            hasher.update(
+                format!(
+                    "{}:{}:{};",
+                    quest.id,
+                    quest.updated_at.and_utc().timestamp_micros(),
+                    quest.adventurer_count
+                )
+            );
+        }
+
+        hasher.update(format!("{}:{}", page.total, page.next_cursor.as_deref().unwrap_or_default()));
+
+        let digest = format!("{:x}", hasher.finalize());
+
+        Self {
+            etag: format!("W/\"{}\"", &digest[..32]).parse().unwrap(),
+            last_modified: None,
+        }
+    }
+
+    /// `If-None-Match` takes precedence, `If-Modified-Since` is only looked at without it.
+    fn is_fresh(&self, headers: &HeaderMap) -> bool {
+        if let Some(if_none_match) = headers.typed_get::<IfNoneMatch>() {
+            return !if_none_match.precondition_passes(&self.etag);
+        }
+
+        match (headers.typed_get::<IfModifiedSince>(), self.last_modified) {
+            (Some(if_modified_since), Some(last_modified)) =>
+                !if_modified_since.is_modified(to_system_time(last_modified)),
+            _ => false,
+        }
+    }
+}
+
+/// `304 Not Modified` when the client's copy is still fresh, `body` as JSON
+/// otherwise. Both carry the validators and `cache_control`.
+pub fn conditional_json<T: Serialize>(
+    request_headers: &HeaderMap,
+    validators: Validators,
+    cache_control: CacheControl,
+    body: &T
+) -> Response {
+    let mut response = if validators.is_fresh(request_headers) {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        (StatusCode::OK, Json(body)).into_response()
+    };
+
+    let headers = response.headers_mut();
+    headers.typed_insert(validators.etag);
+    if let Some(last_modified) = validators.last_modified {
+        headers.typed_insert(LastModified::from(to_system_time(last_modified)));
+    }
+    headers.typed_insert(cache_control);
+
+    response
+}
+
+fn to_system_time(date_time: NaiveDateTime) -> SystemTime {
+    SystemTime::from(date_time.and_utc())
+}
@@ -1,10 +1,15 @@
 use std::{ net::SocketAddr, sync::Arc, time::Duration };
 use crate::{
+    application::caches::board_cache::BoardCache,
     config::config_model::AppConfig,
-    infrastructure::{ axum_http::{default_router, routers}, postgres::postgres_connection::PgPoolSquad },
+    infrastructure::{
+        axum_http::{default_router, routers},
+        postgres::postgres_connection::PgPoolSquad,
+        schedulers,
+    },
 };
 use anyhow::Result;
-use axum::{ Router, http::Method, routing::get };
+use axum::{ Router, http::{ Method, header }, routing::get };
 use tokio::net::TcpListener;
 use tower_http::{
     cors::{ Any, CorsLayer },
@@ -15,14 +20,30 @@ use tower_http::{
 use tracing::info;
 
 pub async fn start(config: Arc<AppConfig>, db_pool: Arc<PgPoolSquad>) -> Result<()> {
+    let board_cache = Arc::new(
+        BoardCache::new(Duration::from_secs(config.board_cache.ttl), config.board_cache.capacity)
+    );
+
+    let quest_deadlines = schedulers::quest_deadlines::spawn(
+        Arc::clone(&db_pool),
+        Arc::clone(&board_cache),
+        Duration::from_secs(config.scheduler.quest_deadlines_interval)
+    );
+    let quest_trash = schedulers::quest_trash::spawn(
+        Arc::clone(&db_pool),
+        Arc::clone(&board_cache),
+        Duration::from_secs(config.scheduler.quest_trash_interval)
+    );
+
     let app = Router::new()
         .fallback(default_router::not_found)
-        .nest("/journey-ledger", routers::journey_ledger::routes(Arc::clone(&db_pool)))
-        .nest("/quest-ops", routers::quest_ops::routes(Arc::clone(&db_pool)))
-        .nest("/crew-switchboard", routers::crew_switchboard::routes(Arc::clone(&db_pool)))
+        .nest("/journey-ledger", routers::journey_ledger::routes(Arc::clone(&db_pool), Arc::clone(&board_cache)))
+        .nest("/quest-ops", routers::quest_ops::routes(Arc::clone(&db_pool), Arc::clone(&board_cache)))
+        .nest("/crew-switchboard", routers::crew_switchboard::routes(Arc::clone(&db_pool), Arc::clone(&board_cache)))
         .nest("/guild-commanders", routers::guild_commanders::routes(Arc::clone(&db_pool)))
         .nest("/adventurers", routers::adventurers::routes(Arc::clone(&db_pool)))
-        .nest("/quest-viewing", routers::quest_viewing::routes(Arc::clone(&db_pool)))
+        .nest("/notifications", routers::notifications::routes(Arc::clone(&db_pool)))
+        .nest("/quest-viewing", routers::quest_viewing::routes(Arc::clone(&db_pool), Arc::clone(&board_cache)))
         .nest("/authentication", routers::authentication::routes(Arc::clone(&db_pool)))
         .route("/health-check", get(default_router::health_check))
         .layer(TimeoutLayer::new(Duration::from_secs(config.server.timeout)))
@@ -37,6 +58,14 @@ pub async fn start(config: Arc<AppConfig>, db_pool: Arc<PgPoolSquad>) -> Result<
                     Method::PATCH,
                     Method::DELETE,
                 ])
+                .allow_headers([
+                    header::AUTHORIZATION,
+                    header::CONTENT_TYPE,
+                    header::IF_MATCH,
+                    header::IF_NONE_MATCH,
+                    header::IF_MODIFIED_SINCE,
+                ])
+                .expose_headers([header::ETAG, header::LAST_MODIFIED])
                 .allow_origin(Any)
         )
         .layer(TraceLayer::new_for_http());
@@ -48,6 +77,9 @@ pub async fn start(config: Arc<AppConfig>, db_pool: Arc<PgPoolSquad>) -> Result<
     info!("Listening on {addr}");
 
     axum::serve(listener, app).with_graceful_shutdown(shutdown_signal()).await?;
+
+    quest_deadlines.abort();
+    quest_trash.abort();
     Ok(())
 }
 
@@ -2,4 +2,5 @@ pub mod routers;
 pub mod http_serve;
 pub mod default_router;
 pub mod response;
-pub mod middlewares;
\ No newline at end of file
+pub mod extractors;
+pub mod http_cache;
\ No newline at end of file
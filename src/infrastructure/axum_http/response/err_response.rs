@@ -1,4 +1,8 @@
+use axum::{ Json, http::StatusCode, response::{ IntoResponse, Response } };
 use serde::Serialize;
+use tracing::error;
+
+use crate::domain::errors::AppError;
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -11,5 +15,49 @@ pub struct ErrResponse<E> {
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ErrMessage {
+    pub code: String,
     pub message: String,
 }
+
+impl AppError {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::Forbidden(_) => StatusCode::FORBIDDEN,
+            AppError::InvalidTransition(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::Validation(_) => StatusCode::BAD_REQUEST,
+            AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            AppError::PreconditionFailed(_) => StatusCode::PRECONDITION_FAILED,
+            AppError::PreconditionRequired(_) => StatusCode::PRECONDITION_REQUIRED,
+            AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let status = self.status_code();
+
+        // Internal details stay in the logs, clients only get a generic message.
+        let message = match &self {
+            AppError::Internal(err) => {
+                error!("Internal error: {:?}", err);
+                "Internal server error".to_string()
+            }
+            err => err.to_string(),
+        };
+
+        (
+            status,
+            Json(ErrResponse {
+                success: false,
+                message: status.canonical_reason().unwrap_or("Error").to_string(),
+                error: ErrMessage {
+                    code: self.code().to_string(),
+                    message,
+                },
+            }),
+        ).into_response()
+    }
+}
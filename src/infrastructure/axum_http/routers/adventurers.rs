@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use axum::{ Json, Router, extract::State, http::StatusCode, response::IntoResponse, routing::post };
+use axum::{ Json, Router, extract::State, http::StatusCode, response::IntoResponse, routing::{ get, post } };
 
 use crate::{
     application::usecases::adventurers::AdventurersUseCase,
@@ -8,9 +8,9 @@ use crate::{
         repositories::adventurers::AdventurersRepository,
         value_objects::adventurer_model::RegisterAdventurerModel,
     },
-    infrastructure::postgres::{
-        postgres_connection::PgPoolSquad,
-        repositories::adventurers::AdventurerPostgres,
+    infrastructure::{
+        axum_http::extractors::CurrentAdventurer,
+        postgres::{ postgres_connection::PgPoolSquad, repositories::adventurers::AdventurerPostgres },
     },
 };
 
@@ -18,7 +18,10 @@ pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
     let adventurers_repository = AdventurerPostgres::new(db_pool);
     let adventurers_use_case = AdventurersUseCase::new(Arc::new(adventurers_repository));
 
-    Router::new().route("/", post(register)).with_state(Arc::new(adventurers_use_case))
+    Router::new()
+        .route("/", post(register))
+        .route("/me/progress", get(progress))
+        .with_state(Arc::new(adventurers_use_case))
 }
 
 pub async fn register<T>(
@@ -33,9 +36,18 @@ pub async fn register<T>(
                 StatusCode::CREATED,
                 format!("Adventurer registered with ID: {}", adventurer_id),
             ).into_response(),
-        Err(err) => {
-            eprintln!("Error registering adventurer: {:?}", err);
-            (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response()
-        }
+        Err(err) => err.into_response(),
+    }
+}
+
+pub async fn progress<T>(
+    State(adventurers_use_case): State<Arc<AdventurersUseCase<T>>>,
+    CurrentAdventurer(adventurer_id): CurrentAdventurer
+) -> impl IntoResponse
+    where T: AdventurersRepository + Send + Sync
+{
+    match adventurers_use_case.progress(adventurer_id).await {
+        Ok(progress) => (StatusCode::OK, Json(progress)).into_response(),
+        Err(err) => err.into_response(),
     }
 }
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use axum::{
-    Json, Router, extract::State, http::{ HeaderMap, HeaderValue, StatusCode, header }, middleware, response::IntoResponse, routing::post
+    Json, Router, extract::State, http::{ HeaderMap, HeaderValue, StatusCode, header }, response::{ IntoResponse, Response }, routing::post
 };
 use axum_extra::extract::cookie::{ Cookie, CookieJar };
 use cookie::time::Duration;
@@ -9,22 +9,26 @@ use cookie::time::Duration;
 use crate::{
     application::usecases::authentication::AuthenticationUseCase,
     config::{ config_loader::{ get_stage }, stage::Stage },
-    domain::repositories::{
-        adventurers::AdventurersRepository,
-        guild_commanders::GuildCommanderRepository,
+    domain::{
+        errors::{ AppError, AppResult },
+        repositories::{
+            adventurers::AdventurersRepository,
+            guild_commanders::GuildCommanderRepository,
+            sessions::SessionsRepository,
+        },
     },
     infrastructure::{
-        axum_http::{middlewares::{adventurers_authorization, guild_commanders_authorization}, response::{
+        axum_http::response::{
             api_response::ApiResponse,
             auth_response::LoginResponse,
-            err_response::{ ErrMessage, ErrResponse },
-        }},
+        },
         jwt_authentication::authentication_model::LoginModel,
         postgres::{
             postgres_connection::PgPoolSquad,
             repositories::{
                 adventurers::AdventurerPostgres,
                 guild_commanders::GuildCommandersPostgres,
+                sessions::SessionsPostgres,
             },
         },
     },
@@ -33,25 +37,34 @@ use crate::{
 pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
     let adventurers_repository = AdventurerPostgres::new(Arc::clone(&db_pool));
     let guild_commanders_repository = GuildCommandersPostgres::new(Arc::clone(&db_pool));
+    let sessions_repository = SessionsPostgres::new(Arc::clone(&db_pool));
     let authentication_use_case = AuthenticationUseCase::new(
         Arc::new(adventurers_repository),
-        Arc::new(guild_commanders_repository)
+        Arc::new(guild_commanders_repository),
+        Arc::new(sessions_repository)
     );
 
     Router::new()
         .route("/adventurers/login", post(adventurers_login))
         .route("/guild-commanders/login", post(guild_commanders_login))
-        .route("/adventurers/refresh_token", post(adventurers_refresh_token).route_layer(middleware::from_fn(adventurers_authorization)))
-        .route("/guild-commanders/refresh_token", post(guild_commanders_refresh_token).route_layer(middleware::from_fn(guild_commanders_authorization)))
+        .route("/adventurers/refresh_token", post(adventurers_refresh_token))
+        .route("/guild-commanders/refresh_token", post(guild_commanders_refresh_token))
+        .route("/adventurers/logout", post(adventurers_logout))
+        .route("/adventurers/logout-all", post(adventurers_logout_all))
+        .route("/guild-commanders/logout", post(guild_commanders_logout))
+        .route("/guild-commanders/logout-all", post(guild_commanders_logout_all))
         .with_state(Arc::new(authentication_use_case))
 }
 
-pub async fn adventurers_login<T1, T2>(
-    State(authentication_use_case): State<Arc<AuthenticationUseCase<T1, T2>>>,
+pub async fn adventurers_login<T1, T2, T3>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T1, T2, T3>>>,
     Json(login_model): Json<LoginModel>
 )
     -> impl IntoResponse
-    where T1: AdventurersRepository + Send + Sync, T2: GuildCommanderRepository + Send + Sync
+    where
+        T1: AdventurersRepository + Send + Sync,
+        T2: GuildCommanderRepository + Send + Sync,
+        T3: SessionsRepository + Send + Sync
 {
     match authentication_use_case.adventurers_login(login_model).await {
         Ok(passport) => {
@@ -96,27 +109,19 @@ pub async fn adventurers_login<T1, T2>(
                 }),
             ).into_response()
         }
-        Err(err) => {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrResponse {
-                    success: false,
-                    message: "Login failed".to_string(),
-                    error: ErrMessage {
-                        message: err.to_string(),
-                    },
-                }),
-            ).into_response()
-        }
+        Err(err) => err.into_response(),
     }
 }
 
-pub async fn adventurers_refresh_token<T1, T2>(
-    State(authentication_use_case): State<Arc<AuthenticationUseCase<T1, T2>>>,
+pub async fn adventurers_refresh_token<T1, T2, T3>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T1, T2, T3>>>,
     jar: CookieJar
 )
     -> impl IntoResponse
-    where T1: AdventurersRepository + Send + Sync, T2: GuildCommanderRepository + Send + Sync
+    where
+        T1: AdventurersRepository + Send + Sync,
+        T2: GuildCommanderRepository + Send + Sync,
+        T3: SessionsRepository + Send + Sync
 {
     if let Some(rft) = jar.get("rft") {
         let refresh_token = rft.value().to_string();
@@ -164,41 +169,24 @@ pub async fn adventurers_refresh_token<T1, T2>(
                     }),
                 ).into_response()
             }
-            Err(err) => {
-                (
-                    StatusCode::UNAUTHORIZED,
-                    Json(ErrResponse {
-                        success: false,
-                        message: "Login failed".to_string(),
-                        error: ErrMessage {
-                            message: err.to_string(),
-                        },
-                    }),
-                ).into_response()
-            }
+            Err(err) => err.into_response(),
         };
 
         return response;
     }
 
-    (
-        StatusCode::UNAUTHORIZED,
-        Json(ErrResponse {
-            success: false,
-            message: "Failed to get refresh token".to_string(),
-            error: ErrMessage {
-                message: "No refresh token cookie found".to_string(),
-            },
-        }),
-    ).into_response()
+    AppError::Unauthorized("No refresh token cookie found".to_string()).into_response()
 }
 
-pub async fn guild_commanders_login<T1, T2>(
-    State(authentication_use_case): State<Arc<AuthenticationUseCase<T1, T2>>>,
+pub async fn guild_commanders_login<T1, T2, T3>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T1, T2, T3>>>,
     Json(login_model): Json<LoginModel>
 )
     -> impl IntoResponse
-    where T1: AdventurersRepository + Send + Sync, T2: GuildCommanderRepository + Send + Sync
+    where
+        T1: AdventurersRepository + Send + Sync,
+        T2: GuildCommanderRepository + Send + Sync,
+        T3: SessionsRepository + Send + Sync
 {
     match authentication_use_case.guild_commanders_login(login_model).await {
         Ok(passport) => {
@@ -232,16 +220,19 @@ pub async fn guild_commanders_login<T1, T2>(
 
             (StatusCode::OK, headers, "Adventurer logged in").into_response()
         }
-        Err(err) => (StatusCode::UNAUTHORIZED, err.to_string()).into_response(),
+        Err(err) => err.into_response(),
     }
 }
 
-pub async fn guild_commanders_refresh_token<T1, T2>(
-    State(authentication_use_case): State<Arc<AuthenticationUseCase<T1, T2>>>,
+pub async fn guild_commanders_refresh_token<T1, T2, T3>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T1, T2, T3>>>,
     jar: CookieJar
 )
     -> impl IntoResponse
-    where T1: AdventurersRepository + Send + Sync, T2: GuildCommanderRepository + Send + Sync
+    where
+        T1: AdventurersRepository + Send + Sync,
+        T2: GuildCommanderRepository + Send + Sync,
+        T3: SessionsRepository + Send + Sync
 {
     if let Some(rft) = jar.get("rft") {
         let refresh_token = rft.value().to_string();
@@ -289,31 +280,118 @@ pub async fn guild_commanders_refresh_token<T1, T2>(
                     }),
                 ).into_response()
             }
-            Err(err) => {
-                (
-                    StatusCode::UNAUTHORIZED,
-                    Json(ErrResponse {
-                        success: false,
-                        message: "Login failed".to_string(),
-                        error: ErrMessage {
-                            message: err.to_string(),
-                        },
-                    }),
-                ).into_response()
-            }
+            Err(err) => err.into_response(),
         };
 
         return response;
     }
 
-    (
-        StatusCode::UNAUTHORIZED,
-        Json(ErrResponse {
-            success: false,
-            message: "Failed to get refresh token".to_string(),
-            error: ErrMessage {
-                message: "No refresh token cookie found".to_string(),
-            },
-        }),
-    ).into_response()
+    AppError::Unauthorized("No refresh token cookie found".to_string()).into_response()
+}
+
+pub async fn adventurers_logout<T1, T2, T3>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T1, T2, T3>>>,
+    jar: CookieJar
+)
+    -> impl IntoResponse
+    where
+        T1: AdventurersRepository + Send + Sync,
+        T2: GuildCommanderRepository + Send + Sync,
+        T3: SessionsRepository + Send + Sync
+{
+    let revoked = match jar.get("rft") {
+        Some(rft) => authentication_use_case.adventurers_logout(rft.value().to_string()).await,
+        None => Ok(()),
+    };
+
+    logged_out_response(revoked, "Logout successful")
+}
+
+pub async fn adventurers_logout_all<T1, T2, T3>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T1, T2, T3>>>,
+    jar: CookieJar
+)
+    -> impl IntoResponse
+    where
+        T1: AdventurersRepository + Send + Sync,
+        T2: GuildCommanderRepository + Send + Sync,
+        T3: SessionsRepository + Send + Sync
+{
+    let revoked = match jar.get("rft") {
+        Some(rft) => authentication_use_case.adventurers_logout_all(rft.value().to_string()).await,
+        None => Err(AppError::Unauthorized("No refresh token cookie found".to_string())),
+    };
+
+    logged_out_response(revoked, "Logged out of all sessions")
+}
+
+pub async fn guild_commanders_logout<T1, T2, T3>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T1, T2, T3>>>,
+    jar: CookieJar
+)
+    -> impl IntoResponse
+    where
+        T1: AdventurersRepository + Send + Sync,
+        T2: GuildCommanderRepository + Send + Sync,
+        T3: SessionsRepository + Send + Sync
+{
+    let revoked = match jar.get("rft") {
+        Some(rft) => authentication_use_case.guild_commanders_logout(rft.value().to_string()).await,
+        None => Ok(()),
+    };
+
+    logged_out_response(revoked, "Logout successful")
+}
+
+pub async fn guild_commanders_logout_all<T1, T2, T3>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T1, T2, T3>>>,
+    jar: CookieJar
+)
+    -> impl IntoResponse
+    where
+        T1: AdventurersRepository + Send + Sync,
+        T2: GuildCommanderRepository + Send + Sync,
+        T3: SessionsRepository + Send + Sync
+{
+    let revoked = match jar.get("rft") {
+        Some(rft) => authentication_use_case.guild_commanders_logout_all(rft.value().to_string()).await,
+        None => Err(AppError::Unauthorized("No refresh token cookie found".to_string())),
+    };
+
+    logged_out_response(revoked, "Logged out of all sessions")
+}
+
+/// Cookies are cleared even when revoking fails, otherwise the client would be
+/// stuck holding dead tokens. The error still decides the status and body.
+fn logged_out_response(revoked: AppResult<()>, message: &str) -> Response {
+    let mut headers = HeaderMap::new();
+
+    for name in ["act", "rft"] {
+        let mut cookie = Cookie::build((name, ""))
+            .path("/")
+            .same_site(cookie::SameSite::Lax)
+            .http_only(true)
+            .max_age(Duration::ZERO);
+
+        if get_stage() == Stage::Production {
+            cookie = cookie.secure(true);
+        }
+
+        headers.append(header::SET_COOKIE, HeaderValue::from_str(&cookie.to_string()).unwrap());
+    }
+
+    match revoked {
+        Ok(_) => {
+            (
+                StatusCode::OK,
+                headers,
+                Json(ApiResponse {
+                    success: true,
+                    message: message.to_string(),
+                    data: (),
+                }),
+            ).into_response()
+        }
+        Err(err) => (headers, err).into_response(),
+    }
 }
@@ -1,59 +1,264 @@
 use std::sync::Arc;
 
-use axum::{ Extension, Router, extract::{ Path, State }, middleware, response::IntoResponse, routing::{delete, post} };
+use axum::{
+    Json, Router, extract::{ Path, Query, State }, response::IntoResponse, routing::{ delete, get, patch, post }
+};
 
 use crate::{
-    application::usecases::crew_switchboard::CrewSwitchboardUseCase,
-    domain::repositories::{
-        crew_switchboard::CrewSwitchboardRepository, quest_viewing::QuestViewingRepository,
+    application::{ caches::board_cache::BoardCache, usecases::crew_switchboard::CrewSwitchboardUseCase },
+    domain::{
+        repositories::{
+            adventurers::AdventurersRepository,
+            crew_switchboard::CrewSwitchboardRepository,
+            quest_viewing::QuestViewingRepository,
+        },
+        value_objects::{
+            ids::{ QuestApplicationId, QuestId, QuestInvitationId },
+            quest_applications::{ ApplicationFilter, ApplyQuestModel },
+            quest_invitations::InviteAdventurerModel,
+            quest_waitlist::{ JoinOptions, JoinOutcome },
+        },
     },
-    infrastructure::{axum_http::middlewares::adventurers_authorization, postgres::{
+    infrastructure::{axum_http::extractors::{ CurrentAdventurer, CurrentGuildCommander }, postgres::{
         postgres_connection::PgPoolSquad,
         repositories::{
+            adventurers::AdventurerPostgres,
             crew_switchboard::CrewSwitchboardPostgres,
             quest_viewing::QuestVieweingPostgres,
         },
     }},
 };
 
-pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
+pub fn routes(db_pool: Arc<PgPoolSquad>, board_cache: Arc<BoardCache>) -> Router {
     let crew_swichboard_repository = CrewSwitchboardPostgres::new(Arc::clone(&db_pool));
     let quest_viewing_repository = QuestVieweingPostgres::new(Arc::clone(&db_pool));
+    let adventurers_repository = AdventurerPostgres::new(db_pool);
     let crew_swichboard_use_case = CrewSwitchboardUseCase::new(
+        Arc::new(crew_swichboard_repository),
         Arc::new(quest_viewing_repository),
-        Arc::new(crew_swichboard_repository)
+        Arc::new(adventurers_repository),
+        board_cache
     );
     Router::new()
     .route("/join/:quest_id", post(join))
     .route("/leave/:quest_id", delete(leave))
-    .route_layer(middleware::from_fn(adventurers_authorization))
+    .route("/waitlist/:quest_id", delete(leave_waitlist))
+    .route("/apply/:quest_id", post(apply))
+    .route("/withdraw/:quest_id", delete(withdraw))
+    .route("/:quest_id/applications", get(applications))
+    .route("/applications/:application_id/approve", patch(approve))
+    .route("/applications/:application_id/reject", patch(reject))
+    .route("/:quest_id/invitations", post(invite))
+    .route("/invitations", get(invitations))
+    .route("/invitations/:invitation_id/accept", patch(accept_invitation))
+    .route("/invitations/:invitation_id/decline", patch(decline_invitation))
     .with_state(Arc::new(crew_swichboard_use_case))
 }
 
-pub async fn join<T1, T2>(
-    State(crew_swichboard_use_case): State<Arc<CrewSwitchboardUseCase<T1, T2>>>,
-    Extension(adventurer_id): Extension<i32>,
-    Path(quest_id): Path<i32>
+pub async fn join<T1, T2, T3>(
+    State(crew_swichboard_use_case): State<Arc<CrewSwitchboardUseCase<T1, T2, T3>>>,
+    CurrentAdventurer(adventurer_id): CurrentAdventurer,
+    Path(quest_id): Path<QuestId>,
+    options: Query<JoinOptions>
 )
     -> impl IntoResponse
-    where T1: QuestViewingRepository + Send + Sync, T2: CrewSwitchboardRepository + Send + Sync
+    where
+        T1: CrewSwitchboardRepository + Send + Sync,
+        T2: QuestViewingRepository + Send + Sync,
+        T3: AdventurersRepository + Send + Sync
 {
-    match crew_swichboard_use_case.join(quest_id, adventurer_id).await {
-        Ok(_) => (axum::http::StatusCode::OK, "Joined the quest successfully").into_response(),
-        Err(e) => (axum::http::StatusCode::BAD_REQUEST, format!("Failed to join the quest: {}", e)).into_response(),
+    match crew_swichboard_use_case.join(quest_id, adventurer_id, &options).await {
+        Ok(JoinOutcome::Joined) => (axum::http::StatusCode::OK, "Joined the quest successfully").into_response(),
+        Ok(outcome) => (axum::http::StatusCode::ACCEPTED, Json(outcome)).into_response(),
+        Err(err) => err.into_response(),
     }
 }
 
-pub async fn leave<T1, T2>(
-    State(crew_swichboard_use_case): State<Arc<CrewSwitchboardUseCase<T1, T2>>>,
-    Extension(adventurer_id): Extension<i32>,
-    Path(quest_id): Path<i32>
+pub async fn leave<T1, T2, T3>(
+    State(crew_swichboard_use_case): State<Arc<CrewSwitchboardUseCase<T1, T2, T3>>>,
+    CurrentAdventurer(adventurer_id): CurrentAdventurer,
+    Path(quest_id): Path<QuestId>
 )
     -> impl IntoResponse
-    where T1: QuestViewingRepository + Send + Sync, T2: CrewSwitchboardRepository + Send + Sync
+    where
+        T1: CrewSwitchboardRepository + Send + Sync,
+        T2: QuestViewingRepository + Send + Sync,
+        T3: AdventurersRepository + Send + Sync
 {
     match crew_swichboard_use_case.leave(quest_id, adventurer_id).await {
         Ok(_) => (axum::http::StatusCode::OK, "Left the quest successfully").into_response(),
-        Err(e) => (axum::http::StatusCode::BAD_REQUEST, format!("Failed to leave the quest: {}", e)).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+pub async fn leave_waitlist<T1, T2, T3>(
+    State(crew_swichboard_use_case): State<Arc<CrewSwitchboardUseCase<T1, T2, T3>>>,
+    CurrentAdventurer(adventurer_id): CurrentAdventurer,
+    Path(quest_id): Path<QuestId>
+)
+    -> impl IntoResponse
+    where
+        T1: CrewSwitchboardRepository + Send + Sync,
+        T2: QuestViewingRepository + Send + Sync,
+        T3: AdventurersRepository + Send + Sync
+{
+    match crew_swichboard_use_case.leave_waitlist(quest_id, adventurer_id).await {
+        Ok(_) => (axum::http::StatusCode::OK, "Left the waitlist successfully").into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+pub async fn apply<T1, T2, T3>(
+    State(crew_swichboard_use_case): State<Arc<CrewSwitchboardUseCase<T1, T2, T3>>>,
+    CurrentAdventurer(adventurer_id): CurrentAdventurer,
+    Path(quest_id): Path<QuestId>,
+    Json(apply_quest_model): Json<ApplyQuestModel>
+)
+    -> impl IntoResponse
+    where
+        T1: CrewSwitchboardRepository + Send + Sync,
+        T2: QuestViewingRepository + Send + Sync,
+        T3: AdventurersRepository + Send + Sync
+{
+    match crew_swichboard_use_case.apply(quest_id, adventurer_id, apply_quest_model).await {
+        Ok(application_id) => (axum::http::StatusCode::CREATED, Json(application_id)).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+pub async fn withdraw<T1, T2, T3>(
+    State(crew_swichboard_use_case): State<Arc<CrewSwitchboardUseCase<T1, T2, T3>>>,
+    CurrentAdventurer(adventurer_id): CurrentAdventurer,
+    Path(quest_id): Path<QuestId>
+)
+    -> impl IntoResponse
+    where
+        T1: CrewSwitchboardRepository + Send + Sync,
+        T2: QuestViewingRepository + Send + Sync,
+        T3: AdventurersRepository + Send + Sync
+{
+    match crew_swichboard_use_case.withdraw(quest_id, adventurer_id).await {
+        Ok(_) => (axum::http::StatusCode::OK, "Withdrew the application successfully").into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+pub async fn applications<T1, T2, T3>(
+    State(crew_swichboard_use_case): State<Arc<CrewSwitchboardUseCase<T1, T2, T3>>>,
+    CurrentGuildCommander(guild_commander_id): CurrentGuildCommander,
+    Path(quest_id): Path<QuestId>,
+    filter: Query<ApplicationFilter>
+)
+    -> impl IntoResponse
+    where
+        T1: CrewSwitchboardRepository + Send + Sync,
+        T2: QuestViewingRepository + Send + Sync,
+        T3: AdventurersRepository + Send + Sync
+{
+    match crew_swichboard_use_case.applications(quest_id, guild_commander_id, &filter).await {
+        Ok(applications) => (axum::http::StatusCode::OK, Json(applications)).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+pub async fn approve<T1, T2, T3>(
+    State(crew_swichboard_use_case): State<Arc<CrewSwitchboardUseCase<T1, T2, T3>>>,
+    CurrentGuildCommander(guild_commander_id): CurrentGuildCommander,
+    Path(application_id): Path<QuestApplicationId>
+)
+    -> impl IntoResponse
+    where
+        T1: CrewSwitchboardRepository + Send + Sync,
+        T2: QuestViewingRepository + Send + Sync,
+        T3: AdventurersRepository + Send + Sync
+{
+    match crew_swichboard_use_case.approve(application_id, guild_commander_id).await {
+        Ok(_) => (axum::http::StatusCode::OK, "Approved the application successfully").into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+pub async fn reject<T1, T2, T3>(
+    State(crew_swichboard_use_case): State<Arc<CrewSwitchboardUseCase<T1, T2, T3>>>,
+    CurrentGuildCommander(guild_commander_id): CurrentGuildCommander,
+    Path(application_id): Path<QuestApplicationId>
+)
+    -> impl IntoResponse
+    where
+        T1: CrewSwitchboardRepository + Send + Sync,
+        T2: QuestViewingRepository + Send + Sync,
+        T3: AdventurersRepository + Send + Sync
+{
+    match crew_swichboard_use_case.reject(application_id, guild_commander_id).await {
+        Ok(_) => (axum::http::StatusCode::OK, "Rejected the application successfully").into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+pub async fn invite<T1, T2, T3>(
+    State(crew_swichboard_use_case): State<Arc<CrewSwitchboardUseCase<T1, T2, T3>>>,
+    CurrentGuildCommander(guild_commander_id): CurrentGuildCommander,
+    Path(quest_id): Path<QuestId>,
+    Json(invite_adventurer_model): Json<InviteAdventurerModel>
+)
+    -> impl IntoResponse
+    where
+        T1: CrewSwitchboardRepository + Send + Sync,
+        T2: QuestViewingRepository + Send + Sync,
+        T3: AdventurersRepository + Send + Sync
+{
+    match crew_swichboard_use_case.invite(quest_id, guild_commander_id, invite_adventurer_model).await {
+        Ok(invitation_id) => (axum::http::StatusCode::CREATED, Json(invitation_id)).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+pub async fn invitations<T1, T2, T3>(
+    State(crew_swichboard_use_case): State<Arc<CrewSwitchboardUseCase<T1, T2, T3>>>,
+    CurrentAdventurer(adventurer_id): CurrentAdventurer
+)
+    -> impl IntoResponse
+    where
+        T1: CrewSwitchboardRepository + Send + Sync,
+        T2: QuestViewingRepository + Send + Sync,
+        T3: AdventurersRepository + Send + Sync
+{
+    match crew_swichboard_use_case.invitations(adventurer_id).await {
+        Ok(invitations) => (axum::http::StatusCode::OK, Json(invitations)).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+pub async fn accept_invitation<T1, T2, T3>(
+    State(crew_swichboard_use_case): State<Arc<CrewSwitchboardUseCase<T1, T2, T3>>>,
+    CurrentAdventurer(adventurer_id): CurrentAdventurer,
+    Path(invitation_id): Path<QuestInvitationId>
+)
+    -> impl IntoResponse
+    where
+        T1: CrewSwitchboardRepository + Send + Sync,
+        T2: QuestViewingRepository + Send + Sync,
+        T3: AdventurersRepository + Send + Sync
+{
+    match crew_swichboard_use_case.accept_invitation(invitation_id, adventurer_id).await {
+        Ok(_) => (axum::http::StatusCode::OK, "Joined the quest successfully").into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+pub async fn decline_invitation<T1, T2, T3>(
+    State(crew_swichboard_use_case): State<Arc<CrewSwitchboardUseCase<T1, T2, T3>>>,
+    CurrentAdventurer(adventurer_id): CurrentAdventurer,
+    Path(invitation_id): Path<QuestInvitationId>
+)
+    -> impl IntoResponse
+    where
+        T1: CrewSwitchboardRepository + Send + Sync,
+        T2: QuestViewingRepository + Send + Sync,
+        T3: AdventurersRepository + Send + Sync
+{
+    match crew_swichboard_use_case.decline_invitation(invitation_id, adventurer_id).await {
+        Ok(_) => (axum::http::StatusCode::OK, "Declined the invitation successfully").into_response(),
+        Err(err) => err.into_response(),
     }
 }
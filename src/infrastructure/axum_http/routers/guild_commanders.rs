@@ -26,9 +26,6 @@ where
                 StatusCode::CREATED,
                 format!("Guild Commander registered with ID: {}", guild_commander_id),
             ).into_response(),
-        Err(err) => {
-            eprintln!("Error registering adventurer: {:?}", err);
-            (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response()
-        }
+        Err(err) => err.into_response(),
     }
 }
\ No newline at end of file
@@ -1,16 +1,19 @@
 use std::sync::Arc;
 
 use axum::{
-    Extension, Router, extract::{ Path, State }, middleware, response::IntoResponse, routing::patch
+    Json, Router, extract::{ Path, State }, response::IntoResponse, routing::{ get, patch }
 };
 
 use crate::{
-    application::usecases::journey_ledger::JourneyLedgerUseCase,
-    domain::repositories::{
-        journey_ledger::JourneyLedgerRepository,
-        quest_viewing::QuestViewingRepository,
+    application::{ caches::board_cache::BoardCache, usecases::journey_ledger::JourneyLedgerUseCase },
+    domain::{
+        repositories::{
+            journey_ledger::JourneyLedgerRepository,
+            quest_viewing::QuestViewingRepository,
+        },
+        value_objects::{ ids::QuestId, quest_transition_model::TransitionQuestModel },
     },
-    infrastructure::{axum_http::middlewares::guild_commanders_authorization, postgres::{
+    infrastructure::{axum_http::extractors::CurrentGuildCommander, postgres::{
         postgres_connection::PgPoolSquad,
         repositories::{
             journey_ledger::JourneyLedgerPostgres,
@@ -19,60 +22,105 @@ use crate::{
     }},
 };
 
-pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
+pub fn routes(db_pool: Arc<PgPoolSquad>, board_cache: Arc<BoardCache>) -> Router {
     let journey_ledger_repository = JourneyLedgerPostgres::new(Arc::clone(&db_pool));
     let quest_viewing_repository = QuestVieweingPostgres::new(Arc::clone(&db_pool));
     let journey_ledger_use_case = JourneyLedgerUseCase::new(
         Arc::new(journey_ledger_repository),
-        Arc::new(quest_viewing_repository)
+        Arc::new(quest_viewing_repository),
+        board_cache
     );
 
     Router::new()
+        .route("/:quest_id/transition", get(allowed_transitions).patch(transition))
+        .route("/:quest_id/history", get(history))
         .route("/in-journey/:quest_id", patch(in_journey))
         .route("/to-completed/:quest_id", patch(to_completed))
         .route("/to-failed/:quest_id", patch(to_failed))
-        .route_layer(middleware::from_fn(guild_commanders_authorization))
         .with_state(Arc::new(journey_ledger_use_case))
 }
 
+pub async fn allowed_transitions<T1, T2>(
+    State(journey_ledger_use_case): State<Arc<JourneyLedgerUseCase<T1, T2>>>,
+    CurrentGuildCommander(guild_commander_id): CurrentGuildCommander,
+    Path(quest_id): Path<QuestId>
+)
+    -> impl IntoResponse
+    where T1: JourneyLedgerRepository + Send + Sync, T2: QuestViewingRepository + Send + Sync
+{
+    match journey_ledger_use_case.allowed_transitions(quest_id, guild_commander_id).await {
+        Ok(transitions) => (axum::http::StatusCode::OK, Json(transitions)).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+pub async fn transition<T1, T2>(
+    State(journey_ledger_use_case): State<Arc<JourneyLedgerUseCase<T1, T2>>>,
+    CurrentGuildCommander(guild_commander_id): CurrentGuildCommander,
+    Path(quest_id): Path<QuestId>,
+    Json(transition_quest_model): Json<TransitionQuestModel>
+)
+    -> impl IntoResponse
+    where T1: JourneyLedgerRepository + Send + Sync, T2: QuestViewingRepository + Send + Sync
+{
+    match journey_ledger_use_case.transition(quest_id, guild_commander_id, transition_quest_model.to, transition_quest_model.reason).await {
+        Ok(transitions) => (axum::http::StatusCode::OK, Json(transitions)).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+pub async fn history<T1, T2>(
+    State(journey_ledger_use_case): State<Arc<JourneyLedgerUseCase<T1, T2>>>,
+    CurrentGuildCommander(guild_commander_id): CurrentGuildCommander,
+    Path(quest_id): Path<QuestId>
+)
+    -> impl IntoResponse
+    where T1: JourneyLedgerRepository + Send + Sync, T2: QuestViewingRepository + Send + Sync
+{
+    match journey_ledger_use_case.history(quest_id, guild_commander_id).await {
+        Ok(events) => (axum::http::StatusCode::OK, Json(events)).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
 pub async fn in_journey<T1, T2>(
     State(journey_ledger_use_case): State<Arc<JourneyLedgerUseCase<T1, T2>>>,
-    Extension(guild_commander_id): Extension<i32>,
-    Path(quest_id): Path<i32>
+    CurrentGuildCommander(guild_commander_id): CurrentGuildCommander,
+    Path(quest_id): Path<QuestId>
 )
     -> impl IntoResponse
     where T1: JourneyLedgerRepository + Send + Sync, T2: QuestViewingRepository + Send + Sync
 {
     match journey_ledger_use_case.in_journey(quest_id, guild_commander_id).await {
         Ok(result) => (axum::http::StatusCode::OK, format!("Quest set to In Journey status. ID: {}", result)).into_response(),
-        Err(err) => (axum::http::StatusCode::BAD_REQUEST, format!("Error: {}", err)).into_response(),
+        Err(err) => err.into_response(),
     }
 }
 
 pub async fn to_completed<T1, T2>(
     State(journey_ledger_use_case): State<Arc<JourneyLedgerUseCase<T1, T2>>>,
-    Extension(guild_commander_id): Extension<i32>,
-    Path(quest_id): Path<i32>
+    CurrentGuildCommander(guild_commander_id): CurrentGuildCommander,
+    Path(quest_id): Path<QuestId>
 )
     -> impl IntoResponse
     where T1: JourneyLedgerRepository + Send + Sync, T2: QuestViewingRepository + Send + Sync
 {
     match journey_ledger_use_case.to_completed(quest_id, guild_commander_id).await {
         Ok(result) => (axum::http::StatusCode::OK, format!("Quest set to Completed status. ID: {}", result)).into_response(),
-        Err(err) => (axum::http::StatusCode::BAD_REQUEST, format!("Error: {}", err)).into_response(),
+        Err(err) => err.into_response(),
     }
 }
 
 pub async fn to_failed<T1, T2>(
     State(journey_ledger_use_case): State<Arc<JourneyLedgerUseCase<T1, T2>>>,
-    Extension(guild_commander_id): Extension<i32>,
-    Path(quest_id): Path<i32>
+    CurrentGuildCommander(guild_commander_id): CurrentGuildCommander,
+    Path(quest_id): Path<QuestId>
 )
     -> impl IntoResponse
     where T1: JourneyLedgerRepository + Send + Sync, T2: QuestViewingRepository + Send + Sync
 {
     match journey_ledger_use_case.to_failed(quest_id, guild_commander_id).await {
         Ok(result) => (axum::http::StatusCode::OK, format!("Quest set to Failed status. ID: {}", result)).into_response(),
-        Err(err) => (axum::http::StatusCode::BAD_REQUEST, format!("Error: {}", err)).into_response(),
+        Err(err) => err.into_response(),
     }
 }
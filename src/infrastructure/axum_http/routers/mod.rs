@@ -2,6 +2,7 @@ pub mod adventurers;
 pub mod authentication;
 pub mod crew_switchboard;
 pub mod guild_commanders;
+pub mod notifications;
 pub mod quest_ops;
 pub mod quest_viewing;
 pub mod journey_ledger;
\ No newline at end of file
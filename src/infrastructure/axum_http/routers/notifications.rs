@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use axum::{ Json, Router, extract::{ Path, Query, State }, response::IntoResponse, routing::{ get, patch } };
+
+use crate::{
+    application::usecases::notifications::NotificationsUseCase,
+    domain::{
+        repositories::notifications::NotificationsRepository,
+        value_objects::{ ids::NotificationId, notification_model::NotificationFilter },
+    },
+    infrastructure::{
+        axum_http::extractors::CurrentAdventurer,
+        postgres::{ postgres_connection::PgPoolSquad, repositories::notifications::NotificationsPostgres },
+    },
+};
+
+pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
+    let notifications_repository = NotificationsPostgres::new(db_pool);
+    let notifications_use_case = NotificationsUseCase::new(Arc::new(notifications_repository));
+
+    Router::new()
+        .route("/", get(list))
+        .route("/:notification_id/read", patch(mark_read))
+        .with_state(Arc::new(notifications_use_case))
+}
+
+pub async fn list<T>(
+    State(notifications_use_case): State<Arc<NotificationsUseCase<T>>>,
+    CurrentAdventurer(adventurer_id): CurrentAdventurer,
+    filter: Query<NotificationFilter>
+) -> impl IntoResponse
+    where T: NotificationsRepository + Send + Sync
+{
+    match notifications_use_case.list(adventurer_id, &filter).await {
+        Ok(notifications) => (axum::http::StatusCode::OK, Json(notifications)).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+pub async fn mark_read<T>(
+    State(notifications_use_case): State<Arc<NotificationsUseCase<T>>>,
+    CurrentAdventurer(adventurer_id): CurrentAdventurer,
+    Path(notification_id): Path<NotificationId>
+) -> impl IntoResponse
+    where T: NotificationsRepository + Send + Sync
+{
+    match notifications_use_case.mark_read(notification_id, adventurer_id).await {
+        Ok(_) => (axum::http::StatusCode::NO_CONTENT).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
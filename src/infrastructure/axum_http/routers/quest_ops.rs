@@ -1,74 +1,118 @@
 use std::sync::Arc;
 
 use axum::{
-    Extension, Json, Router, extract::{ Path, State }, middleware, response::IntoResponse, routing::{ delete, patch, post }
+    Json, Router, extract::{ Path, State }, http::header, response::IntoResponse, routing::{ delete, get, patch, post }
 };
 
 use crate::{
-    application::usecases::quest_ops::QuestOpsUseCase,
+    application::{ caches::board_cache::BoardCache, usecases::quest_ops::QuestOpsUseCase },
     domain::{
+        errors::AppError,
         repositories::{ quest_ops::QuestOpsRepository, quest_viewing::QuestViewingRepository },
-        value_objects::quest_model::{ AddQuestModel, EditQuestModel },
+        value_objects::{ ids::QuestId, quest_model::{ AddQuestModel, EditQuestModel } },
     },
-    infrastructure::{axum_http::middlewares::guild_commanders_authorization, postgres::{
+    infrastructure::{axum_http::extractors::{ CurrentGuildCommander, IfMatch, quest_etag }, postgres::{
         postgres_connection::PgPoolSquad,
         repositories::{ quest_ops::QuestOpsPostgres, quest_viewing::QuestVieweingPostgres },
     }},
 };
 
-pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
+pub fn routes(db_pool: Arc<PgPoolSquad>, board_cache: Arc<BoardCache>) -> Router {
     let quest_ops_repository = QuestOpsPostgres::new(Arc::clone(&db_pool));
     let quest_viewing_repository = QuestVieweingPostgres::new(Arc::clone(&db_pool));
     let quest_ops_use_case = QuestOpsUseCase::new(
         Arc::new(quest_ops_repository),
-        Arc::new(quest_viewing_repository)
+        Arc::new(quest_viewing_repository),
+        board_cache
     );
 
     Router::new()
         .route("/", post(add))
         .route("/:quest_id", patch(edit))
         .route("/:quest_id", delete(remove))
-        .route_layer(middleware::from_fn(guild_commanders_authorization))
+        .route("/trash", get(trash))
+        .route("/:quest_id/restore", post(restore))
         .with_state(Arc::new(quest_ops_use_case))
 }
 
 pub async fn add<T1, T2>(
     State(quest_ops_use_case): State<Arc<QuestOpsUseCase<T1, T2>>>,
+    CurrentGuildCommander(guild_commander_id): CurrentGuildCommander,
     Json(add_quest_model): Json<AddQuestModel>
 )
     -> impl IntoResponse
     where T1: QuestOpsRepository + Send + Sync, T2: QuestViewingRepository + Send + Sync
 {
-    match quest_ops_use_case.add(add_quest_model.guild_commander_id, add_quest_model).await {
+    match quest_ops_use_case.add(guild_commander_id, add_quest_model).await {
         Ok(quest_id) => (axum::http::StatusCode::CREATED, Json(quest_id)).into_response(),
-        Err(err) => (axum::http::StatusCode::INTERNAL_SERVER_ERROR, Json(err.to_string())).into_response(),
+        Err(err) => err.into_response(),
     }
 }
 
 pub async fn edit<T1, T2>(
     State(quest_ops_use_case): State<Arc<QuestOpsUseCase<T1, T2>>>,
-    Path(quest_id): Path<i32>,
+    CurrentGuildCommander(guild_commander_id): CurrentGuildCommander,
+    Path(quest_id): Path<QuestId>,
+    IfMatch(expected_version): IfMatch,
     Json(edit_quest_model): Json<EditQuestModel>
 )
     -> impl IntoResponse
     where T1: QuestOpsRepository + Send + Sync, T2: QuestViewingRepository + Send + Sync
 {
-    match quest_ops_use_case.edit(quest_id, edit_quest_model.guild_commander_id, edit_quest_model).await {
+    match quest_ops_use_case.edit(quest_id, guild_commander_id, expected_version, edit_quest_model).await {
         Ok(edited_quest_id) => (axum::http::StatusCode::OK, Json(edited_quest_id)).into_response(),
-        Err(err) => (axum::http::StatusCode::INTERNAL_SERVER_ERROR, Json(err.to_string())).into_response(),
+        // Hand back what the quest looks like now so the client can merge and retry.
+        Err(AppError::PreconditionFailed(_)) =>
+            match quest_ops_use_case.current(quest_id).await {
+                Ok(quest_model) =>
+                    (
+                        axum::http::StatusCode::PRECONDITION_FAILED,
+                        [(header::ETAG, quest_etag(quest_model.version))],
+                        Json(quest_model),
+                    ).into_response(),
+                Err(err) => err.into_response(),
+            }
+        Err(err) => err.into_response(),
     }
 }
 
 pub async fn remove<T1, T2>(
     State(quest_ops_use_case): State<Arc<QuestOpsUseCase<T1, T2>>>,
-    Extension(guild_commander_id): Extension<i32>,
-    Path(quest_id): Path<i32>
+    CurrentGuildCommander(guild_commander_id): CurrentGuildCommander,
+    Path(quest_id): Path<QuestId>
 )
     -> impl IntoResponse
     where T1: QuestOpsRepository + Send + Sync, T2: QuestViewingRepository + Send + Sync
 {
     match quest_ops_use_case.remove(quest_id, guild_commander_id).await {
         Ok(_) => (axum::http::StatusCode::NO_CONTENT).into_response(),
-        Err(err) => (axum::http::StatusCode::INTERNAL_SERVER_ERROR, Json(err.to_string())).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+pub async fn trash<T1, T2>(
+    State(quest_ops_use_case): State<Arc<QuestOpsUseCase<T1, T2>>>,
+    CurrentGuildCommander(guild_commander_id): CurrentGuildCommander
+)
+    -> impl IntoResponse
+    where T1: QuestOpsRepository + Send + Sync, T2: QuestViewingRepository + Send + Sync
+{
+    match quest_ops_use_case.trash(guild_commander_id).await {
+        Ok(trashed_quests) => (axum::http::StatusCode::OK, Json(trashed_quests)).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+pub async fn restore<T1, T2>(
+    State(quest_ops_use_case): State<Arc<QuestOpsUseCase<T1, T2>>>,
+    CurrentGuildCommander(guild_commander_id): CurrentGuildCommander,
+    Path(quest_id): Path<QuestId>
+)
+    -> impl IntoResponse
+    where T1: QuestOpsRepository + Send + Sync, T2: QuestViewingRepository + Send + Sync
+{
+    match quest_ops_use_case.restore(quest_id, guild_commander_id).await {
+        Ok(restored_quest_id) => (axum::http::StatusCode::OK, Json(restored_quest_id)).into_response(),
+        Err(err) => err.into_response(),
     }
 }
@@ -1,49 +1,115 @@
 use std::sync::Arc;
 
-use axum::{ Router, extract::{ Path, Query, State }, response::IntoResponse, routing::get };
+use axum::{
+    Json, Router, extract::{ Path, Query, State }, http::{ HeaderMap, HeaderValue, header }, response::IntoResponse, routing::get
+};
+use axum_extra::headers::CacheControl;
 
 use crate::{
-    application::usecases::quest_viewing::QuestViewingUseCase,
+    application::{ caches::board_cache::BoardCache, usecases::quest_viewing::QuestViewingUseCase },
     domain::{
         repositories::quest_viewing::QuestViewingRepository,
-        value_objects::board_checking_filter::BoardCheckingFilter,
+        value_objects::{
+            board_checking_filter::BoardCheckingFilter,
+            fuzzy_search_model::FuzzySearchFilter,
+            ids::QuestId,
+        },
     },
-    infrastructure::postgres::{
-        postgres_connection::PgPoolSquad,
-        repositories::quest_viewing::QuestVieweingPostgres,
+    infrastructure::{
+        axum_http::{ extractors::CurrentAdventurer, http_cache::{ Validators, conditional_json } },
+        postgres::{ postgres_connection::PgPoolSquad, repositories::quest_viewing::QuestVieweingPostgres },
     },
 };
 
-pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
+pub fn routes(db_pool: Arc<PgPoolSquad>, board_cache: Arc<BoardCache>) -> Router {
     let quest_viewing_repository = QuestVieweingPostgres::new(db_pool);
-    let adventurers_use_case = QuestViewingUseCase::new(Arc::new(quest_viewing_repository));
+    let adventurers_use_case = QuestViewingUseCase::new(Arc::new(quest_viewing_repository), board_cache);
 
     Router::new()
         .route("/:quest_id", get(view_details))
         .route("/board_checking", get(board_checking))
+        .route("/fuzzy_search", get(fuzzy_search))
+        .route("/tags", get(tags))
         .with_state(Arc::new(adventurers_use_case))
 }
 
 pub async fn view_details<T>(
     State(quest_viewing_use_case): State<Arc<QuestViewingUseCase<T>>>,
-    Path(quest_id): Path<i32>
+    headers: HeaderMap,
+    Path(quest_id): Path<QuestId>
 ) -> impl IntoResponse
     where T: QuestViewingRepository + Send + Sync
 {
     match quest_viewing_use_case.view_details(quest_id).await {
-        Ok(quest_model) => (axum::http::StatusCode::OK, serde_json::to_string(&quest_model).unwrap()),
-        Err(err) => (axum::http::StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+        // Cacheable, but always revalidated: edits need the latest version to match `If-Match`.
+        Ok(quest_model) =>
+            conditional_json(
+                &headers,
+                Validators::for_quest(&quest_model),
+                CacheControl::new().with_public().with_no_cache(),
+                &quest_model
+            ),
+        Err(err) => err.into_response(),
     }
 }
 
 pub async fn board_checking<T>(
     State(quest_viewing_use_case): State<Arc<QuestViewingUseCase<T>>>,
+    adventurer: Option<CurrentAdventurer>,
+    headers: HeaderMap,
     filter: Query<BoardCheckingFilter>
 ) -> impl IntoResponse
     where T: QuestViewingRepository + Send + Sync
 {
-    match quest_viewing_use_case.board_checking(&filter).await {
-        Ok(quests_model) => (axum::http::StatusCode::OK, serde_json::to_string(&quests_model).unwrap()),
-        Err(err) => (axum::http::StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    let adventurer_id = adventurer.map(|CurrentAdventurer(adventurer_id)| adventurer_id);
+
+    match quest_viewing_use_case.board_checking(&filter, adventurer_id).await {
+        Ok(quests_model) => {
+            let cache_control = CacheControl::new().with_max_age(quest_viewing_use_case.board_ttl());
+
+            // An eligible board depends on who is asking, shared caches must not reuse it.
+            let cache_control = if filter.eligible_for_me {
+                cache_control.with_private()
+            } else {
+                cache_control.with_public()
+            };
+
+            let mut response = conditional_json(
+                &headers,
+                Validators::for_board(&quests_model),
+                cache_control,
+                &*quests_model
+            );
+
+            if filter.eligible_for_me {
+                response.headers_mut().append(header::VARY, HeaderValue::from_static("Authorization, Cookie"));
+            }
+
+            response
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+pub async fn fuzzy_search<T>(
+    State(quest_viewing_use_case): State<Arc<QuestViewingUseCase<T>>>,
+    filter: Query<FuzzySearchFilter>
+) -> impl IntoResponse
+    where T: QuestViewingRepository + Send + Sync
+{
+    match quest_viewing_use_case.fuzzy_search(&filter).await {
+        Ok(search_model) => (axum::http::StatusCode::OK, Json(search_model)).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+pub async fn tags<T>(
+    State(quest_viewing_use_case): State<Arc<QuestViewingUseCase<T>>>
+) -> impl IntoResponse
+    where T: QuestViewingRepository + Send + Sync
+{
+    match quest_viewing_use_case.tags().await {
+        Ok(tags_model) => (axum::http::StatusCode::OK, Json(tags_model)).into_response(),
+        Err(err) => err.into_response(),
     }
 }
@@ -1,4 +1,5 @@
 use serde::{ Deserialize, Serialize };
+use strum_macros::Display;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Passport {
@@ -12,10 +13,16 @@ pub struct Claims {
     pub role: Roles,
     pub exp: usize,
     pub iat: usize,
+    /// Unique token id, set on refresh tokens so every rotation yields a distinct token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Display, Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum Roles {
+    #[strum(serialize = "Adventurer")]
     Adventurer,
+
+    #[strum(serialize = "GuildCommander")]
     GuildCommander,
 }
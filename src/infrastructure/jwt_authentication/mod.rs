@@ -1,5 +1,6 @@
 use anyhow::Result;
 use jsonwebtoken::{ DecodingKey, EncodingKey, Header, Validation, decode, encode };
+use sha2::{ Digest, Sha256 };
 
 use jwt_model::Claims;
 
@@ -17,3 +18,8 @@ pub fn verify_token(secret: String, token: String) -> Result<Claims> {
 
     Ok(result.claims)
 }
+
+/// Refresh tokens are only ever stored as their SHA-256 hex digest.
+pub fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
@@ -1,4 +1,5 @@
 pub mod axum_http;
 pub mod postgres;
+pub mod schedulers;
 pub mod argon2_hashing;
 pub mod jwt_authentication;
\ No newline at end of file
@@ -0,0 +1,77 @@
+use diesel::{
+    define_sql_function,
+    dsl,
+    expression::SqlLiteral,
+    infix_operator,
+    pg::Pg,
+    sql_types::{ SingleValue, SqlType, Text },
+};
+
+use crate::infrastructure::postgres::schema::{ quests, sql_types::Tsvector };
+
+/// Text search configuration `quests.search_vector` is generated with.
+/// Queries have to be parsed with the same one or stemming will not line up.
+const SEARCH_CONFIG: &str = "'english'";
+
+const NAME_HEADLINE_OPTIONS: &str = "HighlightAll=true, StartSel=<mark>, StopSel=</mark>";
+const DESCRIPTION_HEADLINE_OPTIONS: &str =
+    "StartSel=<mark>, StopSel=</mark>, MaxWords=35, MinWords=15, MaxFragments=2, FragmentDelimiter=\" ... \"";
+
+#[derive(diesel::query_builder::QueryId, Clone, SqlType)]
+#[diesel(postgres_type(name = "tsquery", schema = "pg_catalog"))]
+pub struct Tsquery;
+
+#[derive(diesel::query_builder::QueryId, Clone, SqlType)]
+#[diesel(postgres_type(name = "regconfig", schema = "pg_catalog"))]
+pub struct Regconfig;
+
+define_sql_function! {
+    fn websearch_to_tsquery(config: Regconfig, query: Text) -> Tsquery;
+}
+
+define_sql_function! {
+    fn ts_rank(vector: Tsvector, query: Tsquery) -> Float;
+}
+
+define_sql_function! {
+    fn ts_headline<T: SingleValue>(config: Regconfig, document: T, query: Tsquery, options: Text) -> T;
+}
+
+infix_operator!(Matches, " @@ ", backend: Pg);
+
+pub type SearchQuery = websearch_to_tsquery<SqlLiteral<Regconfig>, String>;
+
+fn search_config() -> SqlLiteral<Regconfig> {
+    dsl::sql::<Regconfig>(SEARCH_CONFIG)
+}
+
+/// Parses `q` the way web search boxes do: quoted phrases, `or` and `-excluded` words.
+pub fn search_query(q: &str) -> SearchQuery {
+    websearch_to_tsquery(search_config(), q.to_string())
+}
+
+/// `quests.search_vector @@ q`, answered from the GIN index.
+pub fn matches_search(q: &str) -> Matches<quests::search_vector, SearchQuery> {
+    Matches::new(quests::search_vector, search_query(q))
+}
+
+pub fn search_rank(q: &str) -> ts_rank<quests::search_vector, SearchQuery> {
+    ts_rank(quests::search_vector, search_query(q))
+}
+
+/// The whole name with matches wrapped in `<mark>`.
+pub fn name_headline(q: &str) -> ts_headline<Text, SqlLiteral<Regconfig>, quests::name, SearchQuery, &'static str> {
+    ts_headline::<Text, _, _, _, _>(search_config(), quests::name, search_query(q), NAME_HEADLINE_OPTIONS)
+}
+
+/// Up to two fragments of the description around the matches.
+pub fn description_headline(
+    q: &str
+) -> ts_headline<diesel::sql_types::Nullable<Text>, SqlLiteral<Regconfig>, quests::description, SearchQuery, &'static str> {
+    ts_headline::<diesel::sql_types::Nullable<Text>, _, _, _, _>(
+        search_config(),
+        quests::description,
+        search_query(q),
+        DESCRIPTION_HEADLINE_OPTIONS
+    )
+}
@@ -0,0 +1,16 @@
+-- Your SQL goes here
+CREATE TABLE sessions (
+    id SERIAL PRIMARY KEY,
+    family_id UUID NOT NULL,
+    "role" VARCHAR(255) NOT NULL,
+    subject_id INTEGER NOT NULL,
+    refresh_token_hash VARCHAR(64) UNIQUE NOT NULL,
+    expires_at TIMESTAMP NOT NULL,
+    rotated_at TIMESTAMP,
+    revoked_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_sessions_family_id ON sessions (family_id);
+
+CREATE INDEX idx_sessions_subject ON sessions ("role", subject_id);
@@ -0,0 +1,18 @@
+-- Your SQL goes here
+CREATE TABLE quest_status_events (
+    id SERIAL PRIMARY KEY,
+    quest_id INTEGER NOT NULL,
+    from_status VARCHAR(255) NOT NULL,
+    to_status VARCHAR(255) NOT NULL,
+    actor_role VARCHAR(255) NOT NULL,
+    actor_id INTEGER,
+    reason TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+ALTER TABLE
+    quest_status_events
+ADD
+    CONSTRAINT fk_quest FOREIGN KEY (quest_id) REFERENCES quests(id);
+
+CREATE INDEX idx_quest_status_events_quest_id ON quest_status_events (quest_id, created_at);
@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE
+    quests
+DROP
+    CONSTRAINT IF EXISTS chk_quests_crew_size,
+DROP
+    COLUMN IF EXISTS min_crew,
+DROP
+    COLUMN IF EXISTS max_crew;
\ No newline at end of file
@@ -0,0 +1,9 @@
+-- Your SQL goes here
+ALTER TABLE
+    quests
+ADD
+    COLUMN min_crew INTEGER NOT NULL DEFAULT 1,
+ADD
+    COLUMN max_crew INTEGER NOT NULL DEFAULT 5,
+ADD
+    CONSTRAINT chk_quests_crew_size CHECK (min_crew >= 1 AND max_crew >= min_crew);
@@ -0,0 +1,11 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_quests_status_due_at;
+
+ALTER TABLE
+    quests
+DROP
+    CONSTRAINT IF EXISTS chk_quests_schedule,
+DROP
+    COLUMN IF EXISTS starts_at,
+DROP
+    COLUMN IF EXISTS due_at;
\ No newline at end of file
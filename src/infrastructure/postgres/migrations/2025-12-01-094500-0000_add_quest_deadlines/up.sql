@@ -0,0 +1,11 @@
+-- Your SQL goes here
+ALTER TABLE
+    quests
+ADD
+    COLUMN starts_at TIMESTAMP,
+ADD
+    COLUMN due_at TIMESTAMP,
+ADD
+    CONSTRAINT chk_quests_schedule CHECK (starts_at IS NULL OR due_at IS NULL OR due_at > starts_at);
+
+CREATE INDEX idx_quests_status_due_at ON quests ("status", due_at) WHERE deleted_at IS NULL AND due_at IS NOT NULL;
@@ -0,0 +1,20 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE
+    adventurers
+DROP
+    CONSTRAINT IF EXISTS chk_adventurers_progress,
+DROP
+    COLUMN IF EXISTS xp,
+DROP
+    COLUMN IF EXISTS "level",
+DROP
+    COLUMN IF EXISTS gold;
+
+ALTER TABLE
+    quests
+DROP
+    CONSTRAINT IF EXISTS chk_quests_rewards,
+DROP
+    COLUMN IF EXISTS reward_xp,
+DROP
+    COLUMN IF EXISTS reward_gold;
\ No newline at end of file
@@ -0,0 +1,20 @@
+-- Your SQL goes here
+ALTER TABLE
+    quests
+ADD
+    COLUMN reward_xp INTEGER NOT NULL DEFAULT 0,
+ADD
+    COLUMN reward_gold INTEGER NOT NULL DEFAULT 0,
+ADD
+    CONSTRAINT chk_quests_rewards CHECK (reward_xp >= 0 AND reward_gold >= 0);
+
+ALTER TABLE
+    adventurers
+ADD
+    COLUMN xp INTEGER NOT NULL DEFAULT 0,
+ADD
+    COLUMN "level" INTEGER NOT NULL DEFAULT 1,
+ADD
+    COLUMN gold INTEGER NOT NULL DEFAULT 0,
+ADD
+    CONSTRAINT chk_adventurers_progress CHECK (xp >= 0 AND "level" >= 1 AND gold >= 0);
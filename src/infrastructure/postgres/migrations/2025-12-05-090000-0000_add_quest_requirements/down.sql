@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE
+    quests
+DROP
+    CONSTRAINT IF EXISTS chk_quests_min_level,
+DROP
+    COLUMN IF EXISTS min_level,
+DROP
+    COLUMN IF EXISTS min_rank;
\ No newline at end of file
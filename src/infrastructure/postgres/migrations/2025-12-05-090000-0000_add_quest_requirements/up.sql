@@ -0,0 +1,9 @@
+-- Your SQL goes here
+ALTER TABLE
+    quests
+ADD
+    COLUMN min_level INTEGER NOT NULL DEFAULT 1,
+ADD
+    COLUMN min_rank VARCHAR(255),
+ADD
+    CONSTRAINT chk_quests_min_level CHECK (min_level >= 1);
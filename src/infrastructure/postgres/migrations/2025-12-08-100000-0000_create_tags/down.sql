@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS quest_tags;
+DROP TABLE IF EXISTS tags;
\ No newline at end of file
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_quests_search_vector;
+
+ALTER TABLE
+    quests
+DROP
+    COLUMN IF EXISTS search_vector;
\ No newline at end of file
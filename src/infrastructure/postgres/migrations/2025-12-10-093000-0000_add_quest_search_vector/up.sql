@@ -0,0 +1,10 @@
+-- Your SQL goes here
+ALTER TABLE
+    quests
+ADD
+    COLUMN search_vector TSVECTOR GENERATED ALWAYS AS (
+        setweight(to_tsvector('english', coalesce(name, '')), 'A') ||
+        setweight(to_tsvector('english', coalesce(description, '')), 'B')
+    ) STORED;
+
+CREATE INDEX idx_quests_search_vector ON quests USING GIN (search_vector);
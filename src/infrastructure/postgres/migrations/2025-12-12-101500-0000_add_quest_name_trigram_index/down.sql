@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+-- pg_trgm is left installed, other objects in the database may depend on it.
+DROP INDEX IF EXISTS idx_quests_name_trgm;
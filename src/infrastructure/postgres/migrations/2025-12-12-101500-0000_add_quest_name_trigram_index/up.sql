@@ -0,0 +1,4 @@
+-- Your SQL goes here
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX idx_quests_name_trgm ON quests USING GIN (name gin_trgm_ops);
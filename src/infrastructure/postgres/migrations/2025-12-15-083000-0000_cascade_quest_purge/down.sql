@@ -0,0 +1,16 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_quests_deleted_at;
+
+ALTER TABLE
+    quest_status_events
+DROP
+    CONSTRAINT fk_quest,
+ADD
+    CONSTRAINT fk_quest FOREIGN KEY (quest_id) REFERENCES quests(id);
+
+ALTER TABLE
+    quest_adventurer_junction
+DROP
+    CONSTRAINT fk_quest,
+ADD
+    CONSTRAINT fk_quest FOREIGN KEY (quest_id) REFERENCES quests(id);
\ No newline at end of file
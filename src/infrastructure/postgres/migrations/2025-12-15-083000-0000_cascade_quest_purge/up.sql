@@ -0,0 +1,16 @@
+-- Your SQL goes here
+ALTER TABLE
+    quest_adventurer_junction
+DROP
+    CONSTRAINT fk_quest,
+ADD
+    CONSTRAINT fk_quest FOREIGN KEY (quest_id) REFERENCES quests(id) ON DELETE CASCADE;
+
+ALTER TABLE
+    quest_status_events
+DROP
+    CONSTRAINT fk_quest,
+ADD
+    CONSTRAINT fk_quest FOREIGN KEY (quest_id) REFERENCES quests(id) ON DELETE CASCADE;
+
+CREATE INDEX idx_quests_deleted_at ON quests (deleted_at) WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+DROP TRIGGER IF EXISTS trg_quests_bump_version ON quests;
+
+DROP FUNCTION IF EXISTS bump_quest_version();
+
+ALTER TABLE
+    quests
+DROP
+    COLUMN IF EXISTS "version";
\ No newline at end of file
@@ -0,0 +1,16 @@
+-- Your SQL goes here
+ALTER TABLE
+    quests
+ADD
+    COLUMN "version" INTEGER NOT NULL DEFAULT 1;
+
+CREATE FUNCTION bump_quest_version() RETURNS TRIGGER AS $$
+BEGIN
+    NEW."version" := OLD."version" + 1;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_quests_bump_version
+    BEFORE UPDATE ON quests
+    FOR EACH ROW EXECUTE FUNCTION bump_quest_version();
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS quest_applications;
+
+ALTER TABLE
+    quests
+DROP
+    COLUMN IF EXISTS join_policy;
\ No newline at end of file
@@ -0,0 +1,31 @@
+-- Your SQL goes here
+ALTER TABLE
+    quests
+ADD
+    COLUMN join_policy VARCHAR(255) NOT NULL DEFAULT 'Open';
+
+CREATE TABLE quest_applications (
+    id SERIAL PRIMARY KEY,
+    quest_id INTEGER NOT NULL,
+    adventurer_id INTEGER NOT NULL,
+    message TEXT,
+    "status" VARCHAR(255) NOT NULL DEFAULT 'Pending',
+    decided_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+ALTER TABLE
+    quest_applications
+ADD
+    CONSTRAINT fk_quest FOREIGN KEY (quest_id) REFERENCES quests(id) ON DELETE CASCADE;
+
+ALTER TABLE
+    quest_applications
+ADD
+    CONSTRAINT fk_adventurer FOREIGN KEY (adventurer_id) REFERENCES adventurers(id) ON DELETE CASCADE;
+
+-- At most one application per adventurer and quest is waiting for a decision.
+CREATE UNIQUE INDEX idx_quest_applications_pending ON quest_applications (quest_id, adventurer_id) WHERE "status" = 'Pending';
+
+CREATE INDEX idx_quest_applications_quest_id ON quest_applications (quest_id, created_at);
@@ -0,0 +1,32 @@
+-- Your SQL goes here
+CREATE TABLE quest_invitations (
+    id SERIAL PRIMARY KEY,
+    quest_id INTEGER NOT NULL,
+    adventurer_id INTEGER NOT NULL,
+    guild_commander_id INTEGER NOT NULL,
+    "status" VARCHAR(255) NOT NULL DEFAULT 'Pending',
+    expires_at TIMESTAMP NOT NULL,
+    responded_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+ALTER TABLE
+    quest_invitations
+ADD
+    CONSTRAINT fk_quest FOREIGN KEY (quest_id) REFERENCES quests(id) ON DELETE CASCADE;
+
+ALTER TABLE
+    quest_invitations
+ADD
+    CONSTRAINT fk_adventurer FOREIGN KEY (adventurer_id) REFERENCES adventurers(id) ON DELETE CASCADE;
+
+ALTER TABLE
+    quest_invitations
+ADD
+    CONSTRAINT fk_guild_commander FOREIGN KEY (guild_commander_id) REFERENCES guild_commanders(id) ON DELETE CASCADE;
+
+-- At most one invitation per adventurer and quest is waiting for an answer.
+CREATE UNIQUE INDEX idx_quest_invitations_pending ON quest_invitations (quest_id, adventurer_id) WHERE "status" = 'Pending';
+
+CREATE INDEX idx_quest_invitations_adventurer_id ON quest_invitations (adventurer_id, expires_at) WHERE "status" = 'Pending';
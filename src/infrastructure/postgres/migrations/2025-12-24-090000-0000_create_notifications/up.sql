@@ -0,0 +1,22 @@
+-- Your SQL goes here
+CREATE TABLE notifications (
+    id SERIAL PRIMARY KEY,
+    adventurer_id INTEGER NOT NULL,
+    kind VARCHAR(255) NOT NULL,
+    quest_id INTEGER,
+    message TEXT NOT NULL,
+    read_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+ALTER TABLE
+    notifications
+ADD
+    CONSTRAINT fk_adventurer FOREIGN KEY (adventurer_id) REFERENCES adventurers(id) ON DELETE CASCADE;
+
+ALTER TABLE
+    notifications
+ADD
+    CONSTRAINT fk_quest FOREIGN KEY (quest_id) REFERENCES quests(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_notifications_adventurer_id ON notifications (adventurer_id, created_at);
@@ -0,0 +1,21 @@
+-- Your SQL goes here
+CREATE TABLE quest_waitlist (
+    id SERIAL PRIMARY KEY,
+    quest_id INTEGER NOT NULL,
+    adventurer_id INTEGER NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    UNIQUE (quest_id, adventurer_id)
+);
+
+ALTER TABLE
+    quest_waitlist
+ADD
+    CONSTRAINT fk_quest FOREIGN KEY (quest_id) REFERENCES quests(id) ON DELETE CASCADE;
+
+ALTER TABLE
+    quest_waitlist
+ADD
+    CONSTRAINT fk_adventurer FOREIGN KEY (adventurer_id) REFERENCES adventurers(id) ON DELETE CASCADE;
+
+-- The waitlist is served in id order, first come first served.
+CREATE INDEX idx_quest_waitlist_quest_id ON quest_waitlist (quest_id, id);
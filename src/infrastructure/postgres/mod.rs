@@ -1,3 +1,7 @@
 pub mod repositories;
 pub mod postgres_connection;
-pub mod schema;
\ No newline at end of file
+pub mod full_text_search;
+pub mod quest_locking;
+pub mod quest_transitions;
+pub mod schema;
+pub mod trigram_search;
\ No newline at end of file
@@ -1,10 +1,38 @@
+use std::time::Duration;
+
 use diesel::{ PgConnection, r2d2::{ ConnectionManager, Pool } };
 use anyhow::Result;
 
-pub type PgPoolSquad = Pool<ConnectionManager<PgConnection>>;
+use crate::{ config::config_model::Database, domain::errors::{ AppError, AppResult } };
+
+/// r2d2 pool whose connections are only ever used on tokio's blocking thread
+/// pool, so diesel's synchronous I/O never stalls the async workers.
+#[derive(Clone)]
+pub struct PgPoolSquad {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl PgPoolSquad {
+    /// Checks out a connection and runs `query` with it via `spawn_blocking`.
+    pub async fn run<F, T>(&self, query: F) -> AppResult<T>
+        where F: FnOnce(&mut PgConnection) -> AppResult<T> + Send + 'static, T: Send + 'static
+    {
+        let pool = self.pool.clone();
+
+        tokio::task
+            ::spawn_blocking(move || {
+                let mut conn = pool.get()?;
+                query(&mut conn)
+            }).await
+            .map_err(|err| AppError::Internal(err.into()))?
+    }
+}
 
-pub fn establish_connection(database_url: &str) -> Result<PgPoolSquad> {
-    let manager = ConnectionManager::<PgConnection>::new(database_url);
-    let pool = Pool::builder().build(manager)?;
-    Ok(pool)
+pub fn establish_connection(database: &Database) -> Result<PgPoolSquad> {
+    let manager = ConnectionManager::<PgConnection>::new(&database.url);
+    let pool = Pool::builder()
+        .max_size(database.max_connections)
+        .connection_timeout(Duration::from_secs(database.connection_timeout))
+        .build(manager)?;
+    Ok(PgPoolSquad { pool })
 }
@@ -0,0 +1,59 @@
+use diesel::{ PgConnection, define_sql_function, prelude::*, sql_types::BigInt };
+
+use crate::{
+    domain::{
+        entities::quests::QuestEntity,
+        errors::{ AppError, AppResult },
+        value_objects::ids::QuestId,
+    },
+    infrastructure::postgres::schema::{ quest_adventurer_junction, quests },
+};
+
+/// Loads a live quest with `SELECT ... FOR UPDATE`. Crew changes and status
+/// transitions both take this lock first, so for the rest of the transaction
+/// neither the status nor the crew of the quest can change underneath.
+pub fn lock_quest(conn: &mut PgConnection, quest_id: QuestId) -> AppResult<QuestEntity> {
+    let result = quests::table
+        .filter(quests::id.eq(quest_id))
+        .filter(quests::deleted_at.is_null())
+        .select(QuestEntity::as_select())
+        .for_update()
+        .first::<QuestEntity>(conn)
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Quest {} not found", quest_id)))?;
+
+    Ok(result)
+}
+
+/// Gives the quest a new `version` and `updated_at`. Crew changes show up in
+/// the quest's representation, so they have to invalidate its `ETag` and
+/// `Last-Modified` too. The row trigger does the actual increment.
+pub fn bump_version(conn: &mut PgConnection, quest_id: QuestId) -> AppResult<()> {
+    diesel::update(quests::table)
+        .filter(quests::id.eq(quest_id))
+        .set(quests::updated_at.eq(chrono::Utc::now().naive_utc()))
+        .execute(conn)?;
+
+    Ok(())
+}
+
+pub fn count_crew(conn: &mut PgConnection, quest_id: QuestId) -> AppResult<i64> {
+    let result = quest_adventurer_junction::table
+        .filter(quest_adventurer_junction::quest_id.eq(quest_id))
+        .count()
+        .get_result::<i64>(conn)?;
+
+    Ok(result)
+}
+
+define_sql_function! {
+    fn pg_try_advisory_xact_lock(key: BigInt) -> Bool;
+}
+
+/// Takes a transaction scoped advisory lock without waiting. Returns `false`
+/// when another session (possibly another instance) already holds `key`.
+pub fn try_advisory_xact_lock(conn: &mut PgConnection, key: i64) -> AppResult<bool> {
+    let result = diesel::select(pg_try_advisory_xact_lock(key)).get_result::<bool>(conn)?;
+
+    Ok(result)
+}
@@ -0,0 +1,122 @@
+use diesel::{ PgConnection, dsl::{ delete, insert_into, update }, prelude::* };
+
+use crate::{
+    domain::{
+        entities::{ quest_status_events::AddQuestStatusEventEntity, quests::QuestEntity },
+        errors::{ AppError, AppResult },
+        value_objects::{
+            ids::{ AdventurerId, QuestId },
+            progression::{ LevelCurve, ProgressionRules },
+            quest_actor::QuestActor,
+            quest_statuses::{ QuestTransition, TransitionContext, TransitionEffect },
+        },
+    },
+    infrastructure::postgres::{
+        quest_locking::count_crew,
+        schema::{ adventurers, quest_adventurer_junction, quest_status_events, quests },
+    },
+};
+
+/// Moves a quest locked with `lock_quest` along `transition`, applies its
+/// effects and records the status event. Must run inside the locking transaction.
+pub fn apply_transition(
+    conn: &mut PgConnection,
+    quest: &QuestEntity,
+    transition: QuestTransition,
+    actor: QuestActor,
+    reason: Option<String>,
+    progression: ProgressionRules
+) -> AppResult<QuestId> {
+    if quest.status != transition.from {
+        return Err(AppError::Conflict(format!("Quest {} is no longer {}", quest.id, transition.from)));
+    }
+
+    // Guards may have been checked against a snapshot, re-check them now that the crew is locked.
+    transition.check_guards(&TransitionContext::for_quest(quest, count_crew(conn, quest.id)?))?;
+
+    let now = chrono::Utc::now().naive_utc();
+
+    let result = update(quests::table)
+        .filter(quests::id.eq(quest.id))
+        .set((quests::status.eq(transition.to), quests::updated_at.eq(now)))
+        .returning(quests::id)
+        .get_result::<QuestId>(conn)?;
+
+    for effect in transition.effects {
+        match effect {
+            TransitionEffect::ReleaseCrew => {
+                delete(quest_adventurer_junction::table)
+                    .filter(quest_adventurer_junction::quest_id.eq(quest.id))
+                    .execute(conn)?;
+            }
+            TransitionEffect::RewardCrew => {
+                adjust_crew_progress(conn, quest.id, quest.reward_xp, quest.reward_gold, progression.level_curve)?;
+            }
+            TransitionEffect::PenalizeCrew => {
+                adjust_crew_progress(
+                    conn,
+                    quest.id,
+                    -progression.penalty(quest.reward_xp),
+                    -progression.penalty(quest.reward_gold),
+                    progression.level_curve
+                )?;
+            }
+        }
+    }
+
+    insert_into(quest_status_events::table)
+        .values(AddQuestStatusEventEntity {
+            quest_id: quest.id,
+            from_status: transition.from,
+            to_status: transition.to,
+            actor_role: actor.role().to_string(),
+            actor_id: actor.id(),
+            reason,
+            created_at: now,
+        })
+        .execute(conn)?;
+
+    Ok(result)
+}
+
+/// Adds the deltas to the XP and gold of every crew member, never going below
+/// zero, and recomputes their level from the curve.
+fn adjust_crew_progress(
+    conn: &mut PgConnection,
+    quest_id: QuestId,
+    xp_delta: i32,
+    gold_delta: i32,
+    level_curve: LevelCurve
+) -> AppResult<()> {
+    if xp_delta == 0 && gold_delta == 0 {
+        return Ok(());
+    }
+
+    let crew = adventurers::table
+        .filter(
+            adventurers::id.eq_any(
+                quest_adventurer_junction::table
+                    .filter(quest_adventurer_junction::quest_id.eq(quest_id))
+                    .select(quest_adventurer_junction::adventurer_id)
+            )
+        )
+        .select((adventurers::id, adventurers::xp, adventurers::gold))
+        .for_update()
+        .load::<(AdventurerId, i32, i32)>(conn)?;
+
+    for (adventurer_id, xp, gold) in crew {
+        let xp = xp.saturating_add(xp_delta).max(0);
+        let gold = gold.saturating_add(gold_delta).max(0);
+
+        update(adventurers::table)
+            .filter(adventurers::id.eq(adventurer_id))
+            .set((
+                adventurers::xp.eq(xp),
+                adventurers::level.eq(level_curve.level_for_xp(i64::from(xp))),
+                adventurers::gold.eq(gold),
+            ))
+            .execute(conn)?;
+    }
+
+    Ok(())
+}
@@ -1,13 +1,15 @@
 use std::sync::Arc;
 
-use anyhow::Result;
 use axum::async_trait;
-use diesel::{ExpressionMethods, RunQueryDsl, SelectableHelper, insert_into, query_dsl::methods::{FilterDsl, SelectDsl}};
+use diesel::result::{ DatabaseErrorKind, Error as DieselError };
+use diesel::{ExpressionMethods, OptionalExtension, RunQueryDsl, SelectableHelper, insert_into, query_dsl::methods::{FilterDsl, SelectDsl}};
 
 use crate::{
     domain::{
         entities::adventures::{ AdventurerEntity, RegisterAdventurerEntity },
+        errors::{ AppError, AppResult },
         repositories::adventurers::AdventurersRepository,
+        value_objects::ids::AdventurerId,
     },
     infrastructure::postgres::{postgres_connection::PgPoolSquad, schema::adventurers},
 };
@@ -24,25 +26,45 @@ impl AdventurerPostgres {
 
 #[async_trait]
 impl AdventurersRepository for AdventurerPostgres {
-    async fn register(&self, register_adventurers_entity: RegisterAdventurerEntity) -> Result<i32> {
-        let mut conn = Arc::clone(&self.db_pool).get()?;
+    async fn register(&self, register_adventurers_entity: RegisterAdventurerEntity) -> AppResult<AdventurerId> {
+        self.db_pool.run(move |conn| {
+            let result = insert_into(adventurers::table)
+            .values(register_adventurers_entity)
+            .returning(adventurers::id)
+            .get_result::<AdventurerId>(conn)
+            .map_err(|err| match err {
+                DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _) =>
+                    AppError::Conflict("Username is already taken".to_string()),
+                err => err.into(),
+            })?;
 
-        let result = insert_into(adventurers::table)
-        .values(register_adventurers_entity)
-        .returning(adventurers::id)
-        .get_result::<i32>( &mut conn)?;
+            Ok(result)
+        }).await
+    }
 
-        Ok(result)
+    async fn find_by_username(&self, username: String) -> AppResult<AdventurerEntity> {
+        self.db_pool.run(move |conn| {
+            let result = adventurers::table
+                .filter(adventurers::username.eq(&username))
+                .select(AdventurerEntity::as_select())
+                .first::<AdventurerEntity>(conn)
+                .optional()?
+                .ok_or_else(|| AppError::NotFound(format!("Adventurer {} not found", username)))?;
+        
+            Ok(result)
+        }).await
     }
 
-    async fn find_by_username(&self, username: String) -> Result<AdventurerEntity> {
-        let mut conn = Arc::clone(&self.db_pool).get()?;
+    async fn find_by_id(&self, adventurer_id: AdventurerId) -> AppResult<AdventurerEntity> {
+        self.db_pool.run(move |conn| {
+            let result = adventurers::table
+                .filter(adventurers::id.eq(adventurer_id))
+                .select(AdventurerEntity::as_select())
+                .first::<AdventurerEntity>(conn)
+                .optional()?
+                .ok_or_else(|| AppError::NotFound(format!("Adventurer {} not found", adventurer_id)))?;
 
-        let result = adventurers::table
-            .filter(adventurers::username.eq(username))
-            .select(AdventurerEntity::as_select())
-            .first::<AdventurerEntity>(&mut conn)?;
-        
-        Ok(result)
+            Ok(result)
+        }).await
     }
 }
@@ -1,18 +1,60 @@
 use std::sync::Arc;
 
-use anyhow::Result;
 use axum::async_trait;
-use diesel::dsl::{ delete, insert_into };
+use chrono::NaiveDateTime;
+use diesel::dsl::{ delete, exists, insert_into, select, update };
 use diesel::prelude::*;
+use diesel::result::{ DatabaseErrorKind, Error as DieselError };
 
 use crate::{
     domain::{
+        entities::{
+            quest_applications::{
+                AddQuestApplicationEntity,
+                QuestApplicationEntity,
+                QuestApplicationWithAdventurerEntity,
+            },
+            quest_invitations::{
+                AddQuestInvitationEntity,
+                QuestInvitationEntity,
+                QuestInvitationWithQuestEntity,
+            },
+            notifications::AddNotificationEntity,
+            quest_waitlist::AddQuestWaitlistEntity,
+            quests::QuestEntity,
+        },
+        errors::{ AppError, AppResult },
         repositories::crew_switchboard::CrewSwitchboardRepository,
-        value_objects::quest_adventurer_junction::QuestAdventurerJunction,
+        value_objects::{
+            ids::{ AdventurerId, QuestApplicationId, QuestId, QuestInvitationId },
+            join_policy::JoinPolicy,
+            notification_model::NotificationKind,
+            quest_adventurer_junction::{
+                QuestAdventurerJunction,
+                ensure_has_room,
+                ensure_joinable,
+                ensure_leaveable,
+                ensure_open_to_join,
+                ensure_requirements_met,
+                is_full,
+            },
+            quest_applications::{ ApplicationStatus, ensure_pending },
+            quest_invitations::{ InvitationStatus, ensure_answerable },
+            quest_waitlist::JoinOutcome,
+        },
     },
     infrastructure::postgres::{
         postgres_connection::PgPoolSquad,
-        schema::quest_adventurer_junction,
+        quest_locking::{ bump_version, count_crew, lock_quest },
+        schema::{
+            adventurers,
+            notifications,
+            quest_adventurer_junction,
+            quest_applications,
+            quest_invitations,
+            quest_waitlist,
+            quests,
+        },
     },
 };
 
@@ -28,21 +70,513 @@ impl CrewSwitchboardPostgres {
 
 #[async_trait]
 impl CrewSwitchboardRepository for CrewSwitchboardPostgres {
-    async fn join(&self, junction_body: QuestAdventurerJunction) -> Result<()> {
-        let mut conn = Arc::clone(&self.db_pool).get()?;
+    async fn join(&self, junction_body: QuestAdventurerJunction, waitlist: bool) -> AppResult<JoinOutcome> {
+        self.db_pool.run(move |conn| {
+            conn.transaction::<JoinOutcome, AppError, _>(|conn| {
+                let quest = lock_quest(conn, junction_body.quest_id)?;
 
-        insert_into(quest_adventurer_junction::table).values(&junction_body).execute(&mut conn)?;
-        Ok(())
+                ensure_open_to_join(&quest)?;
+                ensure_eligible(conn, &quest, &junction_body)?;
+
+                if is_full(&quest, count_crew(conn, junction_body.quest_id)?) {
+                    if !waitlist {
+                        return Err(
+                            AppError::Conflict(
+                                "Quest is full, join with waitlist=true to queue for a free slot".to_string()
+                            )
+                        );
+                    }
+
+                    return enqueue(conn, &junction_body);
+                }
+
+                add_member(conn, &junction_body)?;
+
+                Ok(JoinOutcome::Joined)
+            })
+        }).await
     }
 
-    async fn leave(&self, junction_body: QuestAdventurerJunction) -> Result<()> {
-        let mut conn = Arc::clone(&self.db_pool).get()?;
+    async fn leave(&self, junction_body: QuestAdventurerJunction) -> AppResult<()> {
+        self.db_pool.run(move |conn| {
+            conn.transaction::<(), AppError, _>(|conn| {
+                let quest = lock_quest(conn, junction_body.quest_id)?;
 
-        delete(quest_adventurer_junction::table)
-            .filter(quest_adventurer_junction::adventurer_id.eq(junction_body.adventurer_id))
+                ensure_leaveable(&quest)?;
+
+                let removed = delete(quest_adventurer_junction::table)
+                    .filter(quest_adventurer_junction::adventurer_id.eq(junction_body.adventurer_id))
+                    .filter(quest_adventurer_junction::quest_id.eq(junction_body.quest_id))
+                    .execute(conn)?;
+
+                if removed == 0 {
+                    return Err(AppError::NotFound("Not a member of this quest's crew".to_string()));
+                }
+
+                bump_version(conn, junction_body.quest_id)?;
+
+                promote_next(conn, &quest)
+            })
+        }).await
+    }
+
+    async fn leave_waitlist(&self, junction_body: QuestAdventurerJunction) -> AppResult<()> {
+        self.db_pool.run(move |conn| {
+            conn.transaction::<(), AppError, _>(|conn| {
+                // Serializes with `promote_next`, which reads the waitlist under this lock.
+                lock_quest(conn, junction_body.quest_id)?;
+
+                let removed = delete(quest_waitlist::table)
+                    .filter(quest_waitlist::quest_id.eq(junction_body.quest_id))
+                    .filter(quest_waitlist::adventurer_id.eq(junction_body.adventurer_id))
+                    .execute(conn)?;
+
+                if removed == 0 {
+                    return Err(AppError::NotFound("Not on the waitlist for this quest".to_string()));
+                }
+
+                Ok(())
+            })
+        }).await
+    }
+
+    async fn apply(&self, application: AddQuestApplicationEntity) -> AppResult<QuestApplicationId> {
+        self.db_pool.run(move |conn| {
+            conn.transaction::<QuestApplicationId, AppError, _>(|conn| {
+                let quest = lock_quest(conn, application.quest_id)?;
+
+                if quest.join_policy == JoinPolicy::Open {
+                    return Err(AppError::Conflict("Quest is open, join it directly".to_string()));
+                }
+
+                ensure_admissible(conn, &quest, &QuestAdventurerJunction {
+                    adventurer_id: application.adventurer_id,
+                    quest_id: application.quest_id,
+                })?;
+
+                let result = insert_into(quest_applications::table)
+                    .values(&application)
+                    .returning(quest_applications::id)
+                    .get_result::<QuestApplicationId>(conn)
+                    .map_err(|err| match err {
+                        DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _) =>
+                            AppError::Conflict("Already applied to this quest".to_string()),
+                        err => err.into(),
+                    })?;
+
+                Ok(result)
+            })
+        }).await
+    }
+
+    async fn withdraw(&self, quest_id: QuestId, adventurer_id: AdventurerId) -> AppResult<()> {
+        self.db_pool.run(move |conn| {
+            let withdrawn = update(quest_applications::table)
+                .filter(quest_applications::quest_id.eq(quest_id))
+                .filter(quest_applications::adventurer_id.eq(adventurer_id))
+                .filter(quest_applications::status.eq(ApplicationStatus::Pending))
+                .set((
+                    quest_applications::status.eq(ApplicationStatus::Withdrawn),
+                    quest_applications::updated_at.eq(chrono::Utc::now().naive_utc()),
+                ))
+                .execute(conn)?;
+
+            if withdrawn == 0 {
+                return Err(AppError::NotFound("No pending application for this quest".to_string()));
+            }
+
+            Ok(())
+        }).await
+    }
+
+    async fn applications(
+        &self,
+        quest_id: QuestId,
+        status: Option<ApplicationStatus>
+    ) -> AppResult<Vec<QuestApplicationWithAdventurerEntity>> {
+        self.db_pool.run(move |conn| {
+            let mut query = quest_applications::table
+                .inner_join(adventurers::table)
+                .filter(quest_applications::quest_id.eq(quest_id))
+                .into_boxed();
+
+            if let Some(status) = status {
+                query = query.filter(quest_applications::status.eq(status));
+            }
+
+            let results = query
+                .order((quest_applications::created_at.asc(), quest_applications::id.asc()))
+                .select((QuestApplicationEntity::as_select(), adventurers::username, adventurers::level))
+                .load::<QuestApplicationWithAdventurerEntity>(conn)?;
+
+            Ok(results)
+        }).await
+    }
+
+    async fn find_application(&self, application_id: QuestApplicationId) -> AppResult<QuestApplicationEntity> {
+        self.db_pool.run(move |conn| {
+            let result = quest_applications::table
+                .find(application_id)
+                .select(QuestApplicationEntity::as_select())
+                .first::<QuestApplicationEntity>(conn)
+                .optional()?
+                .ok_or_else(|| AppError::NotFound(format!("Application {} not found", application_id)))?;
+
+            Ok(result)
+        }).await
+    }
+
+    async fn approve(&self, application_id: QuestApplicationId) -> AppResult<()> {
+        self.db_pool.run(move |conn| {
+            conn.transaction::<(), AppError, _>(|conn| {
+                let quest_id = quest_applications::table
+                    .find(application_id)
+                    .select(quest_applications::quest_id)
+                    .first::<QuestId>(conn)
+                    .optional()?
+                    .ok_or_else(|| AppError::NotFound(format!("Application {} not found", application_id)))?;
+
+                // The quest lock comes first, as for every other crew change.
+                let quest = lock_quest(conn, quest_id)?;
+
+                let application = lock_application(conn, application_id)?;
+
+                ensure_pending(application.status)?;
+
+                admit(conn, &quest, &QuestAdventurerJunction {
+                    adventurer_id: application.adventurer_id,
+                    quest_id,
+                })?;
+
+                decide(conn, application_id, ApplicationStatus::Approved)
+            })
+        }).await
+    }
+
+    async fn reject(&self, application_id: QuestApplicationId) -> AppResult<()> {
+        self.db_pool.run(move |conn| {
+            conn.transaction::<(), AppError, _>(|conn| {
+                let application = lock_application(conn, application_id)?;
+
+                ensure_pending(application.status)?;
+
+                decide(conn, application_id, ApplicationStatus::Rejected)
+            })
+        }).await
+    }
+
+    async fn invite(&self, invitation: AddQuestInvitationEntity) -> AppResult<QuestInvitationId> {
+        self.db_pool.run(move |conn| {
+            conn.transaction::<QuestInvitationId, AppError, _>(|conn| {
+                let quest = lock_quest(conn, invitation.quest_id)?;
+
+                ensure_admissible(conn, &quest, &QuestAdventurerJunction {
+                    adventurer_id: invitation.adventurer_id,
+                    quest_id: invitation.quest_id,
+                })?;
+
+                // Frees the pending slot of the unique index for the new invitation.
+                update(quest_invitations::table)
+                    .filter(quest_invitations::quest_id.eq(invitation.quest_id))
+                    .filter(quest_invitations::adventurer_id.eq(invitation.adventurer_id))
+                    .filter(quest_invitations::status.eq(InvitationStatus::Pending))
+                    .filter(quest_invitations::expires_at.le(invitation.created_at))
+                    .set((
+                        quest_invitations::status.eq(InvitationStatus::Expired),
+                        quest_invitations::updated_at.eq(invitation.created_at),
+                    ))
+                    .execute(conn)?;
+
+                let result = insert_into(quest_invitations::table)
+                    .values(&invitation)
+                    .returning(quest_invitations::id)
+                    .get_result::<QuestInvitationId>(conn)
+                    .map_err(|err| match err {
+                        DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _) =>
+                            AppError::Conflict("Adventurer already has a pending invitation to this quest".to_string()),
+                        err => err.into(),
+                    })?;
+
+                Ok(result)
+            })
+        }).await
+    }
+
+    async fn invitations(
+        &self,
+        adventurer_id: AdventurerId,
+        now: NaiveDateTime
+    ) -> AppResult<Vec<QuestInvitationWithQuestEntity>> {
+        self.db_pool.run(move |conn| {
+            let results = quest_invitations::table
+                .inner_join(quests::table)
+                .filter(quest_invitations::adventurer_id.eq(adventurer_id))
+                .filter(quest_invitations::status.eq(InvitationStatus::Pending))
+                .filter(quest_invitations::expires_at.gt(now))
+                .filter(quests::deleted_at.is_null())
+                .order((quest_invitations::expires_at.asc(), quest_invitations::id.asc()))
+                .select((QuestInvitationEntity::as_select(), quests::name))
+                .load::<QuestInvitationWithQuestEntity>(conn)?;
+
+            Ok(results)
+        }).await
+    }
+
+    async fn accept_invitation(
+        &self,
+        invitation_id: QuestInvitationId,
+        adventurer_id: AdventurerId,
+        now: NaiveDateTime
+    ) -> AppResult<()> {
+        self.db_pool.run(move |conn| {
+            conn.transaction::<(), AppError, _>(|conn| {
+                let quest_id = quest_invitations::table
+                    .filter(quest_invitations::id.eq(invitation_id))
+                    .filter(quest_invitations::adventurer_id.eq(adventurer_id))
+                    .select(quest_invitations::quest_id)
+                    .first::<QuestId>(conn)
+                    .optional()?
+                    .ok_or_else(|| AppError::NotFound(format!("Invitation {} not found", invitation_id)))?;
+
+                // The quest lock comes first, as for every other crew change.
+                let quest = lock_quest(conn, quest_id)?;
+
+                let invitation = lock_invitation(conn, invitation_id, adventurer_id)?;
+
+                ensure_answerable(invitation.status, invitation.expires_at, now)?;
+
+                admit(conn, &quest, &QuestAdventurerJunction {
+                    adventurer_id,
+                    quest_id,
+                })?;
+
+                // A pending application is moot once the adventurer is in the crew.
+                update(quest_applications::table)
+                    .filter(quest_applications::quest_id.eq(quest_id))
+                    .filter(quest_applications::adventurer_id.eq(adventurer_id))
+                    .filter(quest_applications::status.eq(ApplicationStatus::Pending))
+                    .set((
+                        quest_applications::status.eq(ApplicationStatus::Withdrawn),
+                        quest_applications::updated_at.eq(now),
+                    ))
+                    .execute(conn)?;
+
+                respond(conn, invitation_id, InvitationStatus::Accepted, now)
+            })
+        }).await
+    }
+
+    async fn decline_invitation(
+        &self,
+        invitation_id: QuestInvitationId,
+        adventurer_id: AdventurerId,
+        now: NaiveDateTime
+    ) -> AppResult<()> {
+        self.db_pool.run(move |conn| {
+            conn.transaction::<(), AppError, _>(|conn| {
+                let invitation = lock_invitation(conn, invitation_id, adventurer_id)?;
+
+                ensure_answerable(invitation.status, invitation.expires_at, now)?;
+
+                respond(conn, invitation_id, InvitationStatus::Declined, now)
+            })
+        }).await
+    }
+}
+
+/// Every check a new crew member has to pass apart from capacity and the join
+/// policy. The quest must already be locked.
+fn ensure_eligible(
+    conn: &mut PgConnection,
+    quest: &QuestEntity,
+    junction_body: &QuestAdventurerJunction
+) -> AppResult<()> {
+    let already_joined = select(exists(
+        quest_adventurer_junction::table
             .filter(quest_adventurer_junction::quest_id.eq(junction_body.quest_id))
-            .execute(&mut conn)?;
+            .filter(quest_adventurer_junction::adventurer_id.eq(junction_body.adventurer_id))
+    )).get_result::<bool>(conn)?;
+
+    if already_joined {
+        return Err(AppError::Conflict("Already joined this quest".to_string()));
+    }
+
+    ensure_joinable(quest)?;
+
+    let adventurer_level = adventurers::table
+        .find(junction_body.adventurer_id)
+        .select(adventurers::level)
+        .first::<i32>(conn)
+        .optional()?
+        .ok_or_else(||
+            AppError::NotFound(format!("Adventurer {} not found", junction_body.adventurer_id))
+        )?;
+
+    ensure_requirements_met(quest, adventurer_level)
+}
+
+/// `ensure_eligible` and a free slot in the crew.
+fn ensure_admissible(
+    conn: &mut PgConnection,
+    quest: &QuestEntity,
+    junction_body: &QuestAdventurerJunction
+) -> AppResult<()> {
+    ensure_eligible(conn, quest, junction_body)?;
+
+    ensure_has_room(quest, count_crew(conn, junction_body.quest_id)?)
+}
+
+/// Adds the adventurer to the locked quest's crew once `ensure_admissible` passes.
+fn admit(conn: &mut PgConnection, quest: &QuestEntity, junction_body: &QuestAdventurerJunction) -> AppResult<()> {
+    ensure_admissible(conn, quest, junction_body)?;
+
+    add_member(conn, junction_body)
+}
+
+/// Inserts the crew member without any checks, and takes them off the waitlist.
+fn add_member(conn: &mut PgConnection, junction_body: &QuestAdventurerJunction) -> AppResult<()> {
+    insert_into(quest_adventurer_junction::table)
+        .values(junction_body)
+        .execute(conn)
+        .map_err(|err| match err {
+            DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _) =>
+                AppError::Conflict("Already joined this quest".to_string()),
+            err => err.into(),
+        })?;
+
+    delete(quest_waitlist::table)
+        .filter(quest_waitlist::quest_id.eq(junction_body.quest_id))
+        .filter(quest_waitlist::adventurer_id.eq(junction_body.adventurer_id))
+        .execute(conn)?;
 
-        Ok(())
+    bump_version(conn, junction_body.quest_id)
+}
+
+fn enqueue(conn: &mut PgConnection, junction_body: &QuestAdventurerJunction) -> AppResult<JoinOutcome> {
+    let waitlist_id = insert_into(quest_waitlist::table)
+        .values(AddQuestWaitlistEntity {
+            quest_id: junction_body.quest_id,
+            adventurer_id: junction_body.adventurer_id,
+            created_at: chrono::Utc::now().naive_utc(),
+        })
+        .returning(quest_waitlist::id)
+        .get_result::<i32>(conn)
+        .map_err(|err| match err {
+            DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _) =>
+                AppError::Conflict("Already on the waitlist for this quest".to_string()),
+            err => err.into(),
+        })?;
+
+    let position = quest_waitlist::table
+        .filter(quest_waitlist::quest_id.eq(junction_body.quest_id))
+        .filter(quest_waitlist::id.le(waitlist_id))
+        .count()
+        .get_result::<i64>(conn)?;
+
+    Ok(JoinOutcome::Waitlisted { position })
+}
+
+/// Moves the first waiting adventurer who is still eligible into the slot just
+/// freed and notifies them. Those who are not keep their place in the queue.
+/// Nobody is promoted while the quest is still full or has switched to the
+/// `Approval` policy, those waiters stay queued until an open slot comes up.
+fn promote_next(conn: &mut PgConnection, quest: &QuestEntity) -> AppResult<()> {
+    if ensure_open_to_join(quest).is_err() || is_full(quest, count_crew(conn, quest.id)?) {
+        return Ok(());
+    }
+
+    let waiting = quest_waitlist::table
+        .filter(quest_waitlist::quest_id.eq(quest.id))
+        .order(quest_waitlist::id.asc())
+        .select(quest_waitlist::adventurer_id)
+        .load::<AdventurerId>(conn)?;
+
+    for adventurer_id in waiting {
+        let junction_body = QuestAdventurerJunction { adventurer_id, quest_id: quest.id };
+
+        match ensure_eligible(conn, quest, &junction_body) {
+            Ok(()) => {}
+            Err(AppError::Forbidden(_) | AppError::Conflict(_)) => continue,
+            Err(err) => return Err(err),
+        }
+
+        add_member(conn, &junction_body)?;
+
+        insert_into(notifications::table)
+            .values(AddNotificationEntity {
+                adventurer_id,
+                kind: NotificationKind::WaitlistPromoted,
+                quest_id: Some(quest.id),
+                message: format!("A slot opened up on \"{}\", you are now part of the crew", quest.name),
+                created_at: chrono::Utc::now().naive_utc(),
+            })
+            .execute(conn)?;
+
+        break;
     }
+
+    Ok(())
+}
+
+fn lock_application(conn: &mut PgConnection, application_id: QuestApplicationId) -> AppResult<QuestApplicationEntity> {
+    let result = quest_applications::table
+        .find(application_id)
+        .select(QuestApplicationEntity::as_select())
+        .for_update()
+        .first::<QuestApplicationEntity>(conn)
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Application {} not found", application_id)))?;
+
+    Ok(result)
+}
+
+fn decide(conn: &mut PgConnection, application_id: QuestApplicationId, status: ApplicationStatus) -> AppResult<()> {
+    let now = chrono::Utc::now().naive_utc();
+
+    update(quest_applications::table)
+        .filter(quest_applications::id.eq(application_id))
+        .set((
+            quest_applications::status.eq(status),
+            quest_applications::decided_at.eq(now),
+            quest_applications::updated_at.eq(now),
+        ))
+        .execute(conn)?;
+
+    Ok(())
+}
+
+/// Someone else's invitation is reported as missing rather than forbidden.
+fn lock_invitation(
+    conn: &mut PgConnection,
+    invitation_id: QuestInvitationId,
+    adventurer_id: AdventurerId
+) -> AppResult<QuestInvitationEntity> {
+    let result = quest_invitations::table
+        .filter(quest_invitations::id.eq(invitation_id))
+        .filter(quest_invitations::adventurer_id.eq(adventurer_id))
+        .select(QuestInvitationEntity::as_select())
+        .for_update()
+        .first::<QuestInvitationEntity>(conn)
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Invitation {} not found", invitation_id)))?;
+
+    Ok(result)
+}
+
+fn respond(
+    conn: &mut PgConnection,
+    invitation_id: QuestInvitationId,
+    status: InvitationStatus,
+    now: NaiveDateTime
+) -> AppResult<()> {
+    update(quest_invitations::table)
+        .filter(quest_invitations::id.eq(invitation_id))
+        .set((
+            quest_invitations::status.eq(status),
+            quest_invitations::responded_at.eq(now),
+            quest_invitations::updated_at.eq(now),
+        ))
+        .execute(conn)?;
+
+    Ok(())
 }
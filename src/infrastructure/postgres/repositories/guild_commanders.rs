@@ -1,11 +1,11 @@
 use std::sync::Arc;
 
-use anyhow::Result;
 use axum::async_trait;
-use diesel::{ExpressionMethods, RunQueryDsl, SelectableHelper, insert_into, query_dsl::methods::{FilterDsl, SelectDsl}};
+use diesel::result::{ DatabaseErrorKind, Error as DieselError };
+use diesel::{ExpressionMethods, OptionalExtension, RunQueryDsl, SelectableHelper, insert_into, query_dsl::methods::{FilterDsl, SelectDsl}};
 
 use crate::{
-    domain::{entities::guild_commanders::{GuildCommanderEntity, RegisterGuildCommanderEntity}, repositories::guild_commanders::GuildCommanderRepository},
+    domain::{entities::guild_commanders::{GuildCommanderEntity, RegisterGuildCommanderEntity}, errors::{AppError, AppResult}, repositories::guild_commanders::GuildCommanderRepository, value_objects::ids::GuildCommanderId},
     infrastructure::postgres::{postgres_connection::PgPoolSquad, schema::guild_commanders},
 };
 
@@ -24,24 +24,31 @@ impl GuildCommanderRepository for GuildCommandersPostgres {
     async fn register(
         &self,
         register_guild_commander_entity: RegisterGuildCommanderEntity
-    ) -> Result<i32> {
-        let mut conn = Arc::clone(&self.db_pool).get()?;
-
-        let result = insert_into(guild_commanders::table)
-        .values(register_guild_commander_entity)
-        .returning(guild_commanders::id)
-        .get_result::<i32>( &mut conn)?;
-
-        Ok(result)
+    ) -> AppResult<GuildCommanderId> {
+        self.db_pool.run(move |conn| {
+            let result = insert_into(guild_commanders::table)
+            .values(register_guild_commander_entity)
+            .returning(guild_commanders::id)
+            .get_result::<GuildCommanderId>(conn)
+            .map_err(|err| match err {
+                DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _) =>
+                    AppError::Conflict("Username is already taken".to_string()),
+                err => err.into(),
+            })?;
+
+            Ok(result)
+        }).await
     }
-    async fn find_by_username(&self, username: String) -> Result<GuildCommanderEntity> {
-        let mut conn = Arc::clone(&self.db_pool).get()?;
-
-        let result = guild_commanders::table
-            .filter(guild_commanders::username.eq(username))
-            .select(GuildCommanderEntity::as_select())
-            .first::<GuildCommanderEntity>(&mut conn)?;
+    async fn find_by_username(&self, username: String) -> AppResult<GuildCommanderEntity> {
+        self.db_pool.run(move |conn| {
+            let result = guild_commanders::table
+                .filter(guild_commanders::username.eq(&username))
+                .select(GuildCommanderEntity::as_select())
+                .first::<GuildCommanderEntity>(conn)
+                .optional()?
+                .ok_or_else(|| AppError::NotFound(format!("Guild commander {} not found", username)))?;
         
-        Ok(result)
+            Ok(result)
+        }).await
     }
 }
@@ -1,15 +1,27 @@
 use std::sync::Arc;
 
-use anyhow::Result;
 use axum::async_trait;
-use diesel::{ExpressionMethods, dsl::update};
+use diesel::ExpressionMethods;
 use diesel::prelude::*;
 
 use crate::{
     domain::{
-        repositories::journey_ledger::JourneyLedgerRepository, value_objects::quest_statuses::QuestStatus,
+        entities::quest_status_events::QuestStatusEventEntity,
+        errors::{ AppError, AppResult },
+        repositories::journey_ledger::JourneyLedgerRepository,
+        value_objects::{
+            ids::{ GuildCommanderId, QuestId },
+            progression::ProgressionRules,
+            quest_actor::QuestActor,
+            quest_statuses::QuestTransition,
+        },
+    },
+    infrastructure::postgres::{
+        postgres_connection::PgPoolSquad,
+        quest_locking::lock_quest,
+        quest_transitions::apply_transition,
+        schema::quest_status_events,
     },
-    infrastructure::postgres::{postgres_connection::PgPoolSquad, schema::quests},
 };
 
 pub struct JourneyLedgerPostgres {
@@ -24,45 +36,45 @@ impl JourneyLedgerPostgres {
 
 #[async_trait]
 impl JourneyLedgerRepository for JourneyLedgerPostgres {
-    async fn in_journey(&self, quest_id: i32, guild_commander_id: i32) -> Result<i32> {
-        let mut conn = Arc::clone(&self.db_pool).get()?;
-        let result = update(quests::table)
-        .filter(quests::id.eq(quest_id))
-        .filter(quests::deleted_at.is_null())
-        .set((
-            quests::status.eq(QuestStatus::InJourney.to_string()),
-            quests::guild_commander_id.eq(guild_commander_id)
-        ))
-        .returning(quests::id)
-        .get_result::<i32>(&mut conn)?;
-        Ok(result)
-    }
+    async fn transition(
+        &self,
+        quest_id: QuestId,
+        guild_commander_id: GuildCommanderId,
+        transition: QuestTransition,
+        reason: Option<String>,
+        progression: ProgressionRules
+    ) -> AppResult<QuestId> {
+        self.db_pool.run(move |conn| {
+            conn.transaction::<QuestId, AppError, _>(|conn| {
+                let quest = lock_quest(conn, quest_id)?;
 
-    async fn to_complete(&self, quest_id: i32, guild_commander_id: i32) -> Result<i32> {
-        let mut conn = Arc::clone(&self.db_pool).get()?;
-        let result = update(quests::table)
-        .filter(quests::id.eq(quest_id))
-        .filter(quests::deleted_at.is_null())
-        .set((
-            quests::status.eq(QuestStatus::Completed.to_string()),
-            quests::guild_commander_id.eq(guild_commander_id)
-        ))
-        .returning(quests::id)
-        .get_result::<i32>(&mut conn)?;
-        Ok(result)
+                if quest.guild_commander_id != guild_commander_id {
+                    return Err(
+                        AppError::Forbidden(format!("Quest {} belongs to another guild commander", quest_id))
+                    );
+                }
+
+                apply_transition(
+                    conn,
+                    &quest,
+                    transition,
+                    QuestActor::GuildCommander(guild_commander_id),
+                    reason,
+                    progression
+                )
+            })
+        }).await
     }
 
-    async fn to_failed(&self, quest_id: i32, guild_commander_id: i32) -> Result<i32> {
-        let mut conn = Arc::clone(&self.db_pool).get()?;
-        let result = update(quests::table)
-        .filter(quests::id.eq(quest_id))
-        .filter(quests::deleted_at.is_null())
-        .set((
-            quests::status.eq(QuestStatus::Failed.to_string()),
-            quests::guild_commander_id.eq(guild_commander_id)
-        ))
-        .returning(quests::id)
-        .get_result::<i32>(&mut conn)?;
-        Ok(result)
+    async fn history(&self, quest_id: QuestId) -> AppResult<Vec<QuestStatusEventEntity>> {
+        self.db_pool.run(move |conn| {
+            let result = quest_status_events::table
+                .filter(quest_status_events::quest_id.eq(quest_id))
+                .select(QuestStatusEventEntity::as_select())
+                .order((quest_status_events::created_at.asc(), quest_status_events::id.asc()))
+                .load::<QuestStatusEventEntity>(conn)?;
+
+            Ok(result)
+        }).await
     }
 }
@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use diesel::{ dsl::{ exists, select, update }, prelude::* };
+
+use crate::{
+    domain::{
+        entities::notifications::NotificationEntity,
+        errors::{ AppError, AppResult },
+        repositories::notifications::NotificationsRepository,
+        value_objects::ids::{ AdventurerId, NotificationId },
+    },
+    infrastructure::postgres::{ postgres_connection::PgPoolSquad, schema::notifications },
+};
+
+pub struct NotificationsPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl NotificationsPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl NotificationsRepository for NotificationsPostgres {
+    async fn list(
+        &self,
+        adventurer_id: AdventurerId,
+        unread_only: bool,
+        limit: i64
+    ) -> AppResult<Vec<NotificationEntity>> {
+        self.db_pool.run(move |conn| {
+            let mut query = notifications::table
+                .filter(notifications::adventurer_id.eq(adventurer_id))
+                .into_boxed();
+
+            if unread_only {
+                query = query.filter(notifications::read_at.is_null());
+            }
+
+            let results = query
+                .order((notifications::created_at.desc(), notifications::id.desc()))
+                .limit(limit)
+                .select(NotificationEntity::as_select())
+                .load::<NotificationEntity>(conn)?;
+
+            Ok(results)
+        }).await
+    }
+
+    async fn mark_read(&self, notification_id: NotificationId, adventurer_id: AdventurerId) -> AppResult<()> {
+        self.db_pool.run(move |conn| {
+            let found = select(exists(
+                notifications::table
+                    .filter(notifications::id.eq(notification_id))
+                    .filter(notifications::adventurer_id.eq(adventurer_id))
+            )).get_result::<bool>(conn)?;
+
+            if !found {
+                return Err(AppError::NotFound(format!("Notification {} not found", notification_id)));
+            }
+
+            // Keeps the time it was first read.
+            update(notifications::table)
+                .filter(notifications::id.eq(notification_id))
+                .filter(notifications::read_at.is_null())
+                .set(notifications::read_at.eq(chrono::Utc::now().naive_utc()))
+                .execute(conn)?;
+
+            Ok(())
+        }).await
+    }
+}
@@ -0,0 +1,137 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+use crate::{
+    domain::{
+        entities::quests::QuestEntity,
+        errors::{ AppError, AppResult },
+        repositories::quest_deadlines::QuestDeadlinesRepository,
+        value_objects::{
+            ids::QuestId,
+            progression::ProgressionRules,
+            quest_actor::QuestActor,
+            quest_statuses::QuestTransition,
+        },
+    },
+    infrastructure::postgres::{
+        postgres_connection::PgPoolSquad,
+        quest_locking::{ count_crew, try_advisory_xact_lock },
+        quest_transitions::apply_transition,
+        schema::quests,
+    },
+};
+
+/// Advisory lock key shared by every instance running the deadline sweep.
+const QUEST_DEADLINES_LOCK_KEY: i64 = 0x5155_4553_5444_4c4e;
+
+pub struct QuestDeadlinesPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl QuestDeadlinesPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl QuestDeadlinesRepository for QuestDeadlinesPostgres {
+    async fn sweep_overdue(
+        &self,
+        transition: QuestTransition,
+        now: NaiveDateTime,
+        reason: String,
+        progression: ProgressionRules
+    ) -> AppResult<Option<Vec<QuestId>>> {
+        self.db_pool.run(move |conn| {
+            conn.transaction::<Option<Vec<QuestId>>, AppError, _>(|conn| {
+                if !try_advisory_xact_lock(conn, QUEST_DEADLINES_LOCK_KEY)? {
+                    return Ok(None);
+                }
+
+                // Quests a request is busy with are picked up on the next sweep.
+                let overdue = quests::table
+                    .filter(quests::deleted_at.is_null())
+                    .filter(quests::status.eq(transition.from))
+                    .filter(quests::due_at.lt(now))
+                    .select(QuestEntity::as_select())
+                    .for_update()
+                    .skip_locked()
+                    .load::<QuestEntity>(conn)?;
+
+                sweep(conn, overdue, transition, &reason, progression).map(Some)
+            })
+        }).await
+    }
+
+    async fn sweep_unstarted(
+        &self,
+        transition: QuestTransition,
+        now: NaiveDateTime,
+        reason: String,
+        progression: ProgressionRules
+    ) -> AppResult<Option<Vec<QuestId>>> {
+        self.db_pool.run(move |conn| {
+            conn.transaction::<Option<Vec<QuestId>>, AppError, _>(|conn| {
+                if !try_advisory_xact_lock(conn, QUEST_DEADLINES_LOCK_KEY)? {
+                    return Ok(None);
+                }
+
+                let late = quests::table
+                    .filter(quests::deleted_at.is_null())
+                    .filter(quests::status.eq(transition.from))
+                    .filter(quests::starts_at.lt(now))
+                    .select(QuestEntity::as_select())
+                    .for_update()
+                    .skip_locked()
+                    .load::<QuestEntity>(conn)?;
+
+                // A crew big enough to set out is left for its commander to start.
+                let mut understaffed = Vec::with_capacity(late.len());
+
+                for quest in late {
+                    if count_crew(conn, quest.id)? < i64::from(quest.min_crew) {
+                        understaffed.push(quest);
+                    }
+                }
+
+                sweep(conn, understaffed, transition, &reason, progression).map(Some)
+            })
+        }).await
+    }
+}
+
+/// Moves the locked quests along `transition` as the system actor, skipping
+/// those a guard does not hold for.
+fn sweep(
+    conn: &mut PgConnection,
+    quests: Vec<QuestEntity>,
+    transition: QuestTransition,
+    reason: &str,
+    progression: ProgressionRules
+) -> AppResult<Vec<QuestId>> {
+    let mut swept = Vec::with_capacity(quests.len());
+
+    for quest in quests {
+        let applied = apply_transition(
+            conn,
+            &quest,
+            transition,
+            QuestActor::System,
+            Some(reason.to_string()),
+            progression
+        );
+
+        match applied {
+            Ok(quest_id) => swept.push(quest_id),
+            // A guard does not hold for this quest, leave it where it is.
+            Err(AppError::InvalidTransition(_)) => continue,
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(swept)
+}
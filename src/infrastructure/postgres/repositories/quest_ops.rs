@@ -1,15 +1,20 @@
 use std::sync::Arc;
 
-use anyhow::Result;
 use axum::async_trait;
-use diesel::{ ExpressionMethods, RunQueryDsl, insert_into };
+use chrono::NaiveDateTime;
+use diesel::{ PgConnection, dsl::{ exists, not }, prelude::*, insert_into, upsert::excluded };
 
 use crate::{
     domain::{
-        entities::quests::{ AddQuestEntity, EditQuestEntity },
-        repositories::quest_ops::QuestOpsRepository, value_objects::quest_statuses::QuestStatus,
+        entities::{
+            quests::{ AddQuestEntity, EditQuestEntity, TrashedQuestEntity },
+            tags::{ AddTagEntity, QuestTagEntity },
+        },
+        errors::{ AppError, AppResult },
+        repositories::quest_ops::QuestOpsRepository,
+        value_objects::{ ids::{ GuildCommanderId, QuestId }, quest_statuses::EDITABLE_STATUSES },
     },
-    infrastructure::postgres::{ postgres_connection::PgPoolSquad, schema::quests },
+    infrastructure::postgres::{ postgres_connection::PgPoolSquad, schema::{ quest_adventurer_junction, quest_tags, quests, tags } },
 };
 
 pub struct QuestOpsPostgres {
@@ -24,44 +29,196 @@ impl QuestOpsPostgres {
 
 #[async_trait]
 impl QuestOpsRepository for QuestOpsPostgres {
-    async fn add(&self, add_quest_entity: AddQuestEntity) -> Result<i32> {
-        let mut conn = Arc::clone(&self.pg_pool).get()?;
+    async fn add(&self, add_quest_entity: AddQuestEntity, tags: Vec<String>) -> AppResult<QuestId> {
+        self.pg_pool.run(move |conn| {
+            conn.transaction::<QuestId, AppError, _>(|conn| {
+                let result = insert_into(quests::table)
+                    .values(add_quest_entity)
+                    .returning(quests::id)
+                    .get_result::<QuestId>(conn)?;
 
-        let result = insert_into(quests::table)
-            .values(add_quest_entity)
-            .returning(quests::id)
-            .get_result::<i32>(&mut conn)?;
+                replace_tags(conn, result, &tags)?;
 
-        Ok(result)
+                Ok(result)
+            })
+        }).await
     }
 
-    async fn edit(&self, quest_id: i32, edit_quest_entity: EditQuestEntity) -> Result<i32> {
-        let mut conn = Arc::clone(&self.pg_pool).get()?;
+    async fn edit(
+        &self,
+        quest_id: QuestId,
+        edit_quest_entity: EditQuestEntity,
+        tags: Option<Vec<String>>,
+        expected_version: Option<i32>
+    ) -> AppResult<QuestId> {
+        self.pg_pool.run(move |conn| {
+            conn.transaction::<QuestId, AppError, _>(|conn| {
+                let mut query = diesel::update(quests::table)
+                    .filter(quests::id.eq(quest_id))
+                    .filter(quests::deleted_at.is_null())
+                    .filter(quests::status.eq_any(EDITABLE_STATUSES))
+                    .into_boxed();
 
-        let result = diesel::update(quests::table)
-        .filter(quests::id.eq(quest_id))
-        .filter(quests::deleted_at.is_null())
-        .filter(quests::status.eq(QuestStatus::Open.to_string()))
-        .set(edit_quest_entity)
-        .returning(quests::id)
-        .get_result::<i32>(&mut conn)?;
+                if let Some(expected_version) = expected_version {
+                    query = query.filter(quests::version.eq(expected_version));
+                }
 
-        Ok(result)
+                let result = query
+                    .set(edit_quest_entity)
+                    .returning(quests::id)
+                    .get_result::<QuestId>(conn)
+                    .optional()?
+                    .ok_or_else(|| match expected_version {
+                        Some(_) =>
+                            AppError::PreconditionFailed(format!("Quest {} was changed by someone else", quest_id)),
+                        None => AppError::Conflict(format!("Quest {} can no longer be edited", quest_id)),
+                    })?;
+
+                if let Some(tags) = tags {
+                    replace_tags(conn, result, &tags)?;
+                }
+
+                Ok(result)
+            })
+        }).await
     }
 
-    async fn remove(&self, quest_id: i32, guild_commander_id: i32) -> Result<()> {
-        let mut conn = Arc::clone(&self.pg_pool).get()?;
+    async fn remove(&self, quest_id: QuestId, guild_commander_id: GuildCommanderId) -> AppResult<()> {
+        self.pg_pool.run(move |conn| {
+            let removed = diesel::update(quests::table)
+                .filter(quests::id.eq(quest_id))
+                .filter(quests::deleted_at.is_null())
+                .filter(quests::status.eq_any(EDITABLE_STATUSES))
+                .filter(quests::guild_commander_id.eq(guild_commander_id))
+                .filter(
+                    not(
+                        exists(
+                            quest_adventurer_junction::table
+                                .filter(quest_adventurer_junction::quest_id.eq(quest_id))
+                        )
+                    )
+                )
+                .set(quests::deleted_at.eq(chrono::Utc::now().naive_utc()))
+                .execute(conn)?;
+
+            if removed == 0 {
+                return Err(AppError::Conflict(format!("Quest {} can no longer be removed", quest_id)));
+            }
+
+            Ok(())
+        }).await
+    }
+
+    async fn trash(&self, guild_commander_id: GuildCommanderId) -> AppResult<Vec<TrashedQuestEntity>> {
+        self.pg_pool.run(move |conn| {
+            let result = quests::table
+                .filter(quests::deleted_at.is_not_null())
+                .filter(quests::guild_commander_id.eq(guild_commander_id))
+                .order((quests::deleted_at.desc(), quests::id.desc()))
+                .select(TrashedQuestEntity::as_select())
+                .load::<TrashedQuestEntity>(conn)?;
+
+            Ok(result)
+        }).await
+    }
+
+    async fn find_trashed(&self, quest_id: QuestId) -> AppResult<TrashedQuestEntity> {
+        self.pg_pool.run(move |conn| {
+            let result = quests::table
+                .filter(quests::id.eq(quest_id))
+                .filter(quests::deleted_at.is_not_null())
+                .select(TrashedQuestEntity::as_select())
+                .first::<TrashedQuestEntity>(conn)
+                .optional()?
+                .ok_or_else(|| AppError::NotFound(format!("Quest {} is not in the trash", quest_id)))?;
+
+            Ok(result)
+        }).await
+    }
+
+    async fn restore(
+        &self,
+        quest_id: QuestId,
+        guild_commander_id: GuildCommanderId,
+        now: NaiveDateTime
+    ) -> AppResult<QuestId> {
+        self.pg_pool.run(move |conn| {
+            conn.transaction::<QuestId, AppError, _>(|conn| {
+                // Held until the update commits, so the purge job cannot delete it in between.
+                let due_at = quests::table
+                    .filter(quests::id.eq(quest_id))
+                    .filter(quests::deleted_at.is_not_null())
+                    .filter(quests::guild_commander_id.eq(guild_commander_id))
+                    .select(quests::due_at)
+                    .for_update()
+                    .first::<Option<NaiveDateTime>>(conn)
+                    .optional()?
+                    .ok_or_else(|| AppError::NotFound(format!("Quest {} is not in the trash", quest_id)))?;
+
+                // The deadline sweep would cancel it straight away.
+                if due_at.is_some_and(|due_at| due_at <= now) {
+                    return Err(
+                        AppError::Conflict(format!("Cannot restore quest {}, its due date has passed.", quest_id))
+                    );
+                }
 
-        diesel::update(quests::table)
-        .filter(quests::id.eq(quest_id))
-        .filter(quests::deleted_at.is_null())
-        .filter(quests::status.eq(QuestStatus::Open.to_string()))
-        .set((
-            quests::deleted_at.eq(chrono::Utc::now().naive_utc()),
-            quests::guild_commander_id.eq(guild_commander_id),
-        ))
-        .execute(&mut conn)?;
+                let result = diesel::update(quests::table)
+                    .filter(quests::id.eq(quest_id))
+                    .set((
+                        quests::deleted_at.eq(None::<NaiveDateTime>),
+                        quests::updated_at.eq(now),
+                    ))
+                    .returning(quests::id)
+                    .get_result::<QuestId>(conn)?;
 
-        Ok(())
+                Ok(result)
+            })
+        }).await
     }
+
+    async fn purge_trashed(&self, deleted_before: NaiveDateTime) -> AppResult<Vec<QuestId>> {
+        self.pg_pool.run(move |conn| {
+            let result = diesel::delete(quests::table)
+                .filter(quests::deleted_at.lt(deleted_before))
+                .returning(quests::id)
+                .get_results::<QuestId>(conn)?;
+
+            Ok(result)
+        }).await
+    }
+}
+
+/// Points the quest at exactly `names`, creating any tag that does not exist yet.
+fn replace_tags(conn: &mut PgConnection, quest_id: QuestId, names: &[String]) -> AppResult<()> {
+    diesel::delete(quest_tags::table)
+        .filter(quest_tags::quest_id.eq(quest_id))
+        .execute(conn)?;
+
+    if names.is_empty() {
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now().naive_utc();
+    let new_tags = names
+        .iter()
+        .map(|name| AddTagEntity { name: name.clone(), created_at: now })
+        .collect::<Vec<_>>();
+
+    // The no-op update makes RETURNING include tags that already existed.
+    let tag_ids = insert_into(tags::table)
+        .values(&new_tags)
+        .on_conflict(tags::name)
+        .do_update()
+        .set(tags::name.eq(excluded(tags::name)))
+        .returning(tags::id)
+        .get_results::<i32>(conn)?;
+
+    let quest_tags = tag_ids
+        .into_iter()
+        .map(|tag_id| QuestTagEntity { quest_id, tag_id })
+        .collect::<Vec<_>>();
+
+    insert_into(quest_tags::table).values(&quest_tags).execute(conn)?;
+
+    Ok(())
 }
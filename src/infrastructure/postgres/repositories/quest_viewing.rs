@@ -1,21 +1,113 @@
 use std::sync::Arc;
 
-use anyhow::Result;
 use axum::async_trait;
-use diesel::{ prelude::* };
+use diesel::{ dsl, pg::Pg, prelude::*, sql_types::{ Bool, Float, Integer, Text, Varchar } };
 
 use crate::{
     domain::{
-        entities::quests::QuestEntity,
+        entities::{
+            quests::{ QuestEntity, QuestSearchHitEntity, QuestSimilarityEntity, QuestWithCrewCountEntity },
+            tags::{ QuestTagNameEntity, TagUsageEntity },
+        },
+        errors::{ AppError, AppResult },
         repositories::quest_viewing::QuestViewingRepository,
-        value_objects::board_checking_filter::BoardCheckingFilter,
+        value_objects::{
+            board_checking_filter::{ BoardCheckingFilter, BoardSort, SortDirection },
+            adventurer_rank::AdventurerRank,
+            board_cursor::{ BoardCursor, BoardCursorKey },
+            ids::{ AdventurerId, QuestId },
+            join_policy::JoinPolicy,
+            quest_statuses::{ CREW_CHANGEABLE_STATUSES, QuestStatus },
+            tag_model::TagMatch,
+        },
     },
     infrastructure::postgres::{
+        full_text_search::{ description_headline, matches_search, name_headline, search_rank },
         postgres_connection::PgPoolSquad,
-        schema::{ quest_adventurer_junction, quests },
+        schema::{ adventurers, quest_adventurer_junction, quest_tags, quests, tags },
+        trigram_search::{ WordSimilar, set_word_similarity_threshold, word_similarity },
     },
 };
 
+/// Applies the board filters shared by the page query and the total count.
+/// Drafts are not published yet, so they never show up on the board.
+///
+/// `$eligible_for` is the adventurer id and level to keep only quests they could
+/// join directly: the same status, join policy, capacity and requirement checks
+/// `join` makes, minus quests they are already on.
+macro_rules! filter_board {
+    ($query:expr, $filter:expr, $eligible_for:expr) => {{
+        let mut query = $query.filter(quests::status.ne(QuestStatus::Draft));
+
+        if let Some(name) = &$filter.name {
+            query = query.filter(quests::name.ilike(format!("%{}%", name)));
+        }
+
+        if let Some(status) = $filter.status {
+            query = query.filter(quests::status.eq(status));
+        }
+
+        let tag_names = $filter.tag_names();
+
+        if !tag_names.is_empty() {
+            let tagged = quest_tags::table
+                .inner_join(tags::table)
+                .filter(tags::name.eq_any(tag_names.clone()));
+
+            query = match $filter.tag_match {
+                TagMatch::Any => query.filter(quests::id.eq_any(tagged.select(quest_tags::quest_id))),
+                TagMatch::All =>
+                    query.filter(
+                        quests::id.eq_any(
+                            tagged
+                                .group_by(quest_tags::quest_id)
+                                .having(dsl::count(quest_tags::tag_id).eq(tag_names.len() as i64))
+                                .select(quest_tags::quest_id)
+                        )
+                    ),
+            };
+        }
+
+        if let Some((adventurer_id, level)) = $eligible_for {
+            let ranks = AdventurerRank::for_level(level).and_below();
+
+            query = query
+                .filter(quests::status.eq_any(CREW_CHANGEABLE_STATUSES))
+                .filter(quests::join_policy.eq(JoinPolicy::Open))
+                .filter(quests::min_level.le(level))
+                .filter(quests::min_rank.is_null().or(quests::min_rank.eq_any(ranks)))
+                // Correlated on the outer quests row, which works whether or not the
+                // query already joins the junction table.
+                .filter(
+                    dsl::sql::<Bool>(
+                        "NOT EXISTS (SELECT 1 FROM quest_adventurer_junction AS mine \
+                         WHERE mine.quest_id = quests.id AND mine.adventurer_id = "
+                    )
+                        .bind::<Integer, _>(adventurer_id)
+                        .sql(")")
+                )
+                .filter(
+                    dsl::sql::<Bool>(
+                        "(SELECT COUNT(*) FROM quest_adventurer_junction AS crew \
+                         WHERE crew.quest_id = quests.id) < quests.max_crew"
+                    )
+                );
+        }
+
+        if let Some(q) = $filter.search_query() {
+            query = query.filter(matches_search(q));
+        }
+
+        query
+    }};
+}
+
+#[derive(QueryableByName)]
+struct NameWord {
+    #[diesel(sql_type = Text)]
+    name_word: String,
+}
+
 pub struct QuestVieweingPostgres {
     pg_pool: Arc<PgPoolSquad>,
 }
@@ -28,47 +120,255 @@ impl QuestVieweingPostgres {
 
 #[async_trait]
 impl QuestViewingRepository for QuestVieweingPostgres {
-    async fn view_details(&self, quest_id: i32) -> Result<QuestEntity> {
-        let mut conn = Arc::clone(&self.pg_pool).get()?;
+    async fn view_details(&self, quest_id: QuestId) -> AppResult<QuestEntity> {
+        self.pg_pool.run(move |conn| {
+            let result = quests::table
+                .filter(quests::id.eq(quest_id))
+                .filter(quests::deleted_at.is_null())
+                .select(QuestEntity::as_select())
+                .first::<QuestEntity>(conn)
+                .optional()?
+                .ok_or_else(|| AppError::NotFound(format!("Quest {} not found", quest_id)))?;
+
+            Ok(result)
+        }).await
+    }
 
-        let result = quests::table
-            .filter(quests::id.eq(quest_id))
-            .filter(quests::deleted_at.is_null())
-            .select(QuestEntity::as_select())
-            .first::<QuestEntity>(&mut conn)?;
+    async fn view_details_with_crew_count(&self, quest_id: QuestId) -> AppResult<QuestWithCrewCountEntity> {
+        self.pg_pool.run(move |conn| {
+            let result = quests_with_crew_count()
+                .filter(quests::id.eq(quest_id))
+                .first::<QuestWithCrewCountEntity>(conn)
+                .optional()?
+                .ok_or_else(|| AppError::NotFound(format!("Quest {} not found", quest_id)))?;
 
-        Ok(result)
+            Ok(result)
+        }).await
     }
 
-    async fn board_checking(&self, filter: &BoardCheckingFilter) -> Result<Vec<QuestEntity>> {
-        let mut conn = Arc::clone(&self.pg_pool).get()?;
+    async fn board_checking(
+        &self,
+        filter: &BoardCheckingFilter,
+        eligible_for: Option<AdventurerId>,
+        cursor: Option<BoardCursor>,
+        limit: i64
+    ) -> AppResult<Vec<QuestWithCrewCountEntity>> {
+        let filter = filter.clone();
 
-        let mut query = quests::table.filter(quests::deleted_at.is_null()).into_boxed();
+        self.pg_pool.run(move |conn| {
+            let eligible_for = with_level(conn, eligible_for)?;
+            let mut query = filter_board!(quests_with_crew_count(), filter, eligible_for);
 
-        if let Some(name) = &filter.name {
-            query = query.filter(quests::name.ilike(format!("%{}%", name)));
-        }
+            let crew_size = dsl::count(quest_adventurer_junction::adventurer_id.nullable());
+            // Only used by the relevance sort, which the use case allows only with `q`.
+            let rank = search_rank(filter.search_query().unwrap_or_default());
 
-        if let Some(status) = &filter.status {
-            query = query.filter(quests::status.eq(status.to_string()));
-        }
+            // Keyset pagination: continue strictly after the last row of the previous
+            // page, using the quest id to break ties between equal sort keys.
+            if let Some(cursor) = cursor {
+                let id = cursor.id;
+
+                query = match (cursor.key, cursor.direction) {
+                    (BoardCursorKey::CreatedAt(key), SortDirection::Asc) =>
+                        query.filter(quests::created_at.gt(key).or(quests::created_at.eq(key).and(quests::id.gt(id)))),
+                    (BoardCursorKey::CreatedAt(key), SortDirection::Desc) =>
+                        query.filter(quests::created_at.lt(key).or(quests::created_at.eq(key).and(quests::id.lt(id)))),
+                    (BoardCursorKey::UpdatedAt(key), SortDirection::Asc) =>
+                        query.filter(quests::updated_at.gt(key).or(quests::updated_at.eq(key).and(quests::id.gt(id)))),
+                    (BoardCursorKey::UpdatedAt(key), SortDirection::Desc) =>
+                        query.filter(quests::updated_at.lt(key).or(quests::updated_at.eq(key).and(quests::id.lt(id)))),
+                    (BoardCursorKey::Name(key), SortDirection::Asc) =>
+                        query.filter(quests::name.gt(key.clone()).or(quests::name.eq(key).and(quests::id.gt(id)))),
+                    (BoardCursorKey::Name(key), SortDirection::Desc) =>
+                        query.filter(quests::name.lt(key.clone()).or(quests::name.eq(key).and(quests::id.lt(id)))),
+                    (BoardCursorKey::CrewSize(key), SortDirection::Asc) =>
+                        query.having(crew_size.gt(key).or(crew_size.eq(key).and(quests::id.gt(id)))),
+                    (BoardCursorKey::CrewSize(key), SortDirection::Desc) =>
+                        query.having(crew_size.lt(key).or(crew_size.eq(key).and(quests::id.lt(id)))),
+                    (BoardCursorKey::Relevance(key), SortDirection::Asc) =>
+                        query.filter(rank.clone().gt(key).or(rank.clone().eq(key).and(quests::id.gt(id)))),
+                    (BoardCursorKey::Relevance(key), SortDirection::Desc) =>
+                        query.filter(rank.clone().lt(key).or(rank.clone().eq(key).and(quests::id.lt(id)))),
+                };
+            }
 
-        let result = query
-            .select(QuestEntity::as_select())
-            .order(quests::created_at.desc())
-            .load::<QuestEntity>(&mut conn)?;
+            query = match (filter.sort(), filter.direction) {
+                (BoardSort::CreatedAt, SortDirection::Asc) => query.order((quests::created_at.asc(), quests::id.asc())),
+                (BoardSort::CreatedAt, SortDirection::Desc) => query.order((quests::created_at.desc(), quests::id.desc())),
+                (BoardSort::UpdatedAt, SortDirection::Asc) => query.order((quests::updated_at.asc(), quests::id.asc())),
+                (BoardSort::UpdatedAt, SortDirection::Desc) => query.order((quests::updated_at.desc(), quests::id.desc())),
+                (BoardSort::Name, SortDirection::Asc) => query.order((quests::name.asc(), quests::id.asc())),
+                (BoardSort::Name, SortDirection::Desc) => query.order((quests::name.desc(), quests::id.desc())),
+                (BoardSort::CrewSize, SortDirection::Asc) => query.order((crew_size.asc(), quests::id.asc())),
+                (BoardSort::CrewSize, SortDirection::Desc) => query.order((crew_size.desc(), quests::id.desc())),
+                (BoardSort::Relevance, SortDirection::Asc) => query.order((rank.asc(), quests::id.asc())),
+                (BoardSort::Relevance, SortDirection::Desc) => query.order((rank.desc(), quests::id.desc())),
+            };
 
-        Ok(result)
+            let result = query
+                .limit(limit)
+                .load::<QuestWithCrewCountEntity>(conn)?;
+
+            Ok(result)
+        }).await
     }
 
-    async fn adventurers_counting_by_quest_id(&self, quest_id: i32) -> Result<i64> {
-        let mut conn = Arc::clone(&self.pg_pool).get()?;
+    async fn board_counting(
+        &self,
+        filter: &BoardCheckingFilter,
+        eligible_for: Option<AdventurerId>
+    ) -> AppResult<i64> {
+        let filter = filter.clone();
+
+        self.pg_pool.run(move |conn| {
+            let eligible_for = with_level(conn, eligible_for)?;
+            let query = filter_board!(
+                quests::table.filter(quests::deleted_at.is_null()).into_boxed(),
+                filter,
+                eligible_for
+            );
 
-        let result = quest_adventurer_junction::table
-            .count()
-            .filter(quest_adventurer_junction::quest_id.eq(quest_id))
-            .first::<i64>(&mut conn)?;
+            let result = query.count().get_result::<i64>(conn)?;
 
-        Ok(result)
+            Ok(result)
+        }).await
     }
+
+    async fn adventurers_counting_by_quest_id(&self, quest_id: QuestId) -> AppResult<i64> {
+        self.pg_pool.run(move |conn| {
+            let result = quest_adventurer_junction::table
+                .count()
+                .filter(quest_adventurer_junction::quest_id.eq(quest_id))
+                .first::<i64>(conn)?;
+
+            Ok(result)
+        }).await
+    }
+
+    async fn search_hits(&self, quest_ids: Vec<QuestId>, q: String) -> AppResult<Vec<QuestSearchHitEntity>> {
+        self.pg_pool.run(move |conn| {
+            let result = quests::table
+                .filter(quests::id.eq_any(quest_ids))
+                .select((quests::id, search_rank(&q), name_headline(&q), description_headline(&q)))
+                .load::<QuestSearchHitEntity>(conn)?;
+
+            Ok(result)
+        }).await
+    }
+
+    async fn fuzzy_search(&self, q: String, threshold: f32, limit: i64) -> AppResult<Vec<QuestSimilarityEntity>> {
+        self.pg_pool.run(move |conn| {
+            conn.transaction::<_, AppError, _>(|conn| {
+                set_word_similarity_threshold(conn, threshold)?;
+
+                let score = word_similarity(q.clone(), quests::name);
+
+                let result = quests_with_crew_count()
+                    .filter(quests::status.ne(QuestStatus::Draft))
+                    .filter(WordSimilar::new(q.clone().into_sql::<Text>(), quests::name))
+                    .order((score.clone().desc(), quests::id.desc()))
+                    .select((QuestWithCrewCountEntity::as_select(), score))
+                    .limit(limit)
+                    .load::<QuestSimilarityEntity>(conn)?;
+
+                Ok(result)
+            })
+        }).await
+    }
+
+    async fn closest_name_word(&self, word: String, threshold: f32) -> AppResult<Option<String>> {
+        self.pg_pool.run(move |conn| {
+            let result = diesel::sql_query(
+                "SELECT name_word FROM ( \
+                     SELECT DISTINCT regexp_split_to_table(lower(name), '[^[:alnum:]]+') AS name_word \
+                     FROM quests WHERE deleted_at IS NULL AND status <> $1 \
+                 ) AS name_words \
+                 WHERE name_word <> '' AND similarity(name_word, $2) >= $3 \
+                 ORDER BY similarity(name_word, $2) DESC, name_word \
+                 LIMIT 1"
+            )
+                .bind::<Varchar, _>(QuestStatus::Draft)
+                .bind::<Text, _>(word)
+                .bind::<Float, _>(threshold)
+                .get_result::<NameWord>(conn)
+                .optional()?;
+
+            Ok(result.map(|row| row.name_word))
+        }).await
+    }
+
+    async fn tags_by_quest_ids(&self, quest_ids: Vec<QuestId>) -> AppResult<Vec<QuestTagNameEntity>> {
+        self.pg_pool.run(move |conn| {
+            let result = quest_tags::table
+                .inner_join(tags::table)
+                .filter(quest_tags::quest_id.eq_any(quest_ids))
+                .order((quest_tags::quest_id.asc(), tags::name.asc()))
+                .select((quest_tags::quest_id, tags::name))
+                .load::<QuestTagNameEntity>(conn)?;
+
+            Ok(result)
+        }).await
+    }
+
+    async fn tag_usage(&self) -> AppResult<Vec<TagUsageEntity>> {
+        self.pg_pool.run(move |conn| {
+            let quest_count = dsl::count(quest_tags::quest_id);
+
+            let result = tags::table
+                .inner_join(quest_tags::table.inner_join(quests::table))
+                .filter(quests::deleted_at.is_null())
+                .filter(quests::status.ne(QuestStatus::Draft))
+                .group_by(tags::id)
+                .order((quest_count.desc(), tags::name.asc()))
+                .select((tags::name, quest_count))
+                .load::<TagUsageEntity>(conn)?;
+
+            Ok(result)
+        }).await
+    }
+}
+
+type QuestsWithCrewCount<'a> = dsl::IntoBoxed<
+    'a,
+    dsl::Select<
+        dsl::GroupBy<
+            dsl::Filter<
+                dsl::LeftJoin<quests::table, quest_adventurer_junction::table>,
+                dsl::IsNull<quests::deleted_at>
+            >,
+            quests::id
+        >,
+        dsl::AsSelect<QuestWithCrewCountEntity, Pg>
+    >,
+    Pg
+>;
+
+/// Live quests left joined to their crew and grouped per quest, so quests
+/// nobody has joined yet still come back with a count of zero.
+fn quests_with_crew_count<'a>() -> QuestsWithCrewCount<'a> {
+    quests::table
+        .left_join(quest_adventurer_junction::table)
+        .filter(quests::deleted_at.is_null())
+        .group_by(quests::id)
+        .select(QuestWithCrewCountEntity::as_select())
+        .into_boxed()
+}
+
+/// Pairs the adventurer with their current level for the eligibility filter.
+fn with_level(
+    conn: &mut PgConnection,
+    adventurer_id: Option<AdventurerId>
+) -> AppResult<Option<(AdventurerId, i32)>> {
+    let Some(adventurer_id) = adventurer_id else {
+        return Ok(None);
+    };
+
+    let level = adventurers::table
+        .find(adventurer_id)
+        .select(adventurers::level)
+        .first::<i32>(conn)
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Adventurer {} not found", adventurer_id)))?;
+
+    Ok(Some((adventurer_id, level)))
 }
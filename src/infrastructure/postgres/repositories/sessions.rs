@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use diesel::{ dsl::{ insert_into, update }, prelude::* };
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        entities::sessions::{ AddSessionEntity, SessionEntity },
+        errors::{ AppError, AppResult },
+        repositories::sessions::SessionsRepository,
+    },
+    infrastructure::postgres::{ postgres_connection::PgPoolSquad, schema::sessions },
+};
+
+pub struct SessionsPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl SessionsPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl SessionsRepository for SessionsPostgres {
+    async fn create(&self, add_session_entity: AddSessionEntity) -> AppResult<i32> {
+        self.db_pool.run(move |conn| {
+            let result = insert_into(sessions::table)
+                .values(add_session_entity)
+                .returning(sessions::id)
+                .get_result::<i32>(conn)?;
+
+            Ok(result)
+        }).await
+    }
+
+    async fn find_by_token_hash(&self, refresh_token_hash: String) -> AppResult<SessionEntity> {
+        self.db_pool.run(move |conn| {
+            let result = sessions::table
+                .filter(sessions::refresh_token_hash.eq(refresh_token_hash))
+                .select(SessionEntity::as_select())
+                .first::<SessionEntity>(conn)
+                .optional()?
+                .ok_or_else(|| AppError::NotFound("Session not found".to_string()))?;
+
+            Ok(result)
+        }).await
+    }
+
+    async fn rotate(&self, session_id: i32, add_session_entity: AddSessionEntity) -> AppResult<i32> {
+        self.db_pool.run(move |conn| {
+            conn.transaction::<i32, AppError, _>(|conn| {
+                let rotated = update(sessions::table)
+                    .filter(sessions::id.eq(session_id))
+                    .filter(sessions::rotated_at.is_null())
+                    .filter(sessions::revoked_at.is_null())
+                    .set(sessions::rotated_at.eq(chrono::Utc::now().naive_utc()))
+                    .execute(conn)?;
+
+                if rotated == 0 {
+                    return Err(AppError::Conflict("Session has already been rotated".to_string()));
+                }
+
+                let result = insert_into(sessions::table)
+                    .values(add_session_entity)
+                    .returning(sessions::id)
+                    .get_result::<i32>(conn)?;
+
+                Ok(result)
+            })
+        }).await
+    }
+
+    async fn revoke_family(&self, family_id: Uuid) -> AppResult<()> {
+        self.db_pool.run(move |conn| {
+            update(sessions::table)
+                .filter(sessions::family_id.eq(family_id))
+                .filter(sessions::revoked_at.is_null())
+                .set(sessions::revoked_at.eq(chrono::Utc::now().naive_utc()))
+                .execute(conn)?;
+
+            Ok(())
+        }).await
+    }
+
+    async fn revoke_all_by_subject(&self, role: String, subject_id: i32) -> AppResult<()> {
+        self.db_pool.run(move |conn| {
+            update(sessions::table)
+                .filter(sessions::role.eq(role))
+                .filter(sessions::subject_id.eq(subject_id))
+                .filter(sessions::revoked_at.is_null())
+                .set(sessions::revoked_at.eq(chrono::Utc::now().naive_utc()))
+                .execute(conn)?;
+
+            Ok(())
+        }).await
+    }
+}
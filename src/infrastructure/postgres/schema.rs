@@ -1,5 +1,11 @@
 // @generated automatically by Diesel CLI.
 
+pub mod sql_types {
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "tsvector", schema = "pg_catalog"))]
+    pub struct Tsvector;
+}
+
 diesel::table! {
     adventurers (id) {
         id -> Int4,
@@ -9,6 +15,9 @@ diesel::table! {
         password -> Varchar,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        xp -> Int4,
+        level -> Int4,
+        gold -> Int4,
     }
 }
 
@@ -24,6 +33,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    notifications (id) {
+        id -> Int4,
+        adventurer_id -> Int4,
+        #[max_length = 255]
+        kind -> Varchar,
+        quest_id -> Nullable<Int4>,
+        message -> Text,
+        read_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     quest_adventurer_junction (quest_id, adventurer_id) {
         quest_id -> Int4,
@@ -32,6 +54,70 @@ diesel::table! {
 }
 
 diesel::table! {
+    quest_applications (id) {
+        id -> Int4,
+        quest_id -> Int4,
+        adventurer_id -> Int4,
+        message -> Nullable<Text>,
+        #[max_length = 255]
+        status -> Varchar,
+        decided_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    quest_invitations (id) {
+        id -> Int4,
+        quest_id -> Int4,
+        adventurer_id -> Int4,
+        guild_commander_id -> Int4,
+        #[max_length = 255]
+        status -> Varchar,
+        expires_at -> Timestamp,
+        responded_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    quest_status_events (id) {
+        id -> Int4,
+        quest_id -> Int4,
+        #[max_length = 255]
+        from_status -> Varchar,
+        #[max_length = 255]
+        to_status -> Varchar,
+        #[max_length = 255]
+        actor_role -> Varchar,
+        actor_id -> Nullable<Int4>,
+        reason -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    quest_tags (quest_id, tag_id) {
+        quest_id -> Int4,
+        tag_id -> Int4,
+    }
+}
+
+diesel::table! {
+    quest_waitlist (id) {
+        id -> Int4,
+        quest_id -> Int4,
+        adventurer_id -> Int4,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::Tsvector;
+
     quests (id) {
         id -> Int4,
         #[max_length = 255]
@@ -43,16 +129,74 @@ diesel::table! {
         created_at -> Timestamp,
         updated_at -> Timestamp,
         deleted_at -> Nullable<Timestamp>,
+        min_crew -> Int4,
+        max_crew -> Int4,
+        starts_at -> Nullable<Timestamp>,
+        due_at -> Nullable<Timestamp>,
+        reward_xp -> Int4,
+        reward_gold -> Int4,
+        min_level -> Int4,
+        #[max_length = 255]
+        min_rank -> Nullable<Varchar>,
+        search_vector -> Tsvector,
+        version -> Int4,
+        #[max_length = 255]
+        join_policy -> Varchar,
+    }
+}
+
+diesel::table! {
+    sessions (id) {
+        id -> Int4,
+        family_id -> Uuid,
+        #[max_length = 255]
+        role -> Varchar,
+        subject_id -> Int4,
+        #[max_length = 64]
+        refresh_token_hash -> Varchar,
+        expires_at -> Timestamp,
+        rotated_at -> Nullable<Timestamp>,
+        revoked_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    tags (id) {
+        id -> Int4,
+        #[max_length = 64]
+        name -> Varchar,
+        created_at -> Timestamp,
     }
 }
 
+diesel::joinable!(notifications -> adventurers (adventurer_id));
+diesel::joinable!(notifications -> quests (quest_id));
 diesel::joinable!(quest_adventurer_junction -> adventurers (adventurer_id));
 diesel::joinable!(quest_adventurer_junction -> quests (quest_id));
+diesel::joinable!(quest_applications -> adventurers (adventurer_id));
+diesel::joinable!(quest_applications -> quests (quest_id));
+diesel::joinable!(quest_invitations -> adventurers (adventurer_id));
+diesel::joinable!(quest_invitations -> guild_commanders (guild_commander_id));
+diesel::joinable!(quest_invitations -> quests (quest_id));
+diesel::joinable!(quest_status_events -> quests (quest_id));
+diesel::joinable!(quest_tags -> quests (quest_id));
+diesel::joinable!(quest_tags -> tags (tag_id));
+diesel::joinable!(quest_waitlist -> adventurers (adventurer_id));
+diesel::joinable!(quest_waitlist -> quests (quest_id));
 diesel::joinable!(quests -> guild_commanders (guild_commander_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     adventurers,
     guild_commanders,
+    notifications,
     quest_adventurer_junction,
+    quest_applications,
+    quest_invitations,
+    quest_status_events,
+    quest_tags,
+    quest_waitlist,
     quests,
+    sessions,
+    tags,
 );
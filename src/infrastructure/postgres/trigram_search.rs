@@ -0,0 +1,25 @@
+use diesel::{ PgConnection, define_sql_function, infix_operator, pg::Pg, prelude::*, sql_types::{ Bool, Text } };
+
+use crate::domain::errors::AppResult;
+
+define_sql_function! {
+    /// Greatest `pg_trgm` similarity between `term` and any run of words in `document`.
+    fn word_similarity(term: Text, document: Text) -> Float;
+}
+
+define_sql_function! {
+    fn set_config(setting: Text, value: Text, is_local: Bool) -> Text;
+}
+
+// `term <% document`, true when `word_similarity` reaches
+// `pg_trgm.word_similarity_threshold`. Unlike calling the function this can use
+// the GIN trigram index.
+infix_operator!(WordSimilar, " <% ", backend: Pg);
+
+/// Sets the `<%` threshold for the rest of the current transaction.
+pub fn set_word_similarity_threshold(conn: &mut PgConnection, threshold: f32) -> AppResult<()> {
+    diesel::select(set_config("pg_trgm.word_similarity_threshold", threshold.to_string(), true))
+        .execute(conn)?;
+
+    Ok(())
+}
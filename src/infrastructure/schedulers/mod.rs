@@ -0,0 +1,2 @@
+pub mod quest_deadlines;
+pub mod quest_trash;
\ No newline at end of file
@@ -0,0 +1,40 @@
+use std::{ sync::Arc, time::Duration };
+
+use tokio::{ task::JoinHandle, time::MissedTickBehavior };
+use tracing::{ error, info };
+
+use crate::{
+    application::{ caches::board_cache::BoardCache, usecases::quest_deadlines::QuestDeadlinesUseCase },
+    infrastructure::postgres::{
+        postgres_connection::PgPoolSquad,
+        repositories::quest_deadlines::QuestDeadlinesPostgres,
+    },
+};
+
+/// Sweeps for overdue quests every `interval` until the returned task is aborted.
+/// Every instance runs it, the advisory lock in the repository keeps sweeps
+/// from overlapping.
+pub fn spawn(db_pool: Arc<PgPoolSquad>, board_cache: Arc<BoardCache>, interval: Duration) -> JoinHandle<()> {
+    let quest_deadlines_repository = QuestDeadlinesPostgres::new(db_pool);
+    let quest_deadlines_use_case = QuestDeadlinesUseCase::new(
+        Arc::new(quest_deadlines_repository),
+        board_cache
+    );
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+
+            match quest_deadlines_use_case.sweep_overdue().await {
+                Ok(quest_ids) if !quest_ids.is_empty() => {
+                    info!("Moved {} overdue quests: {:?}", quest_ids.len(), quest_ids);
+                }
+                Ok(_) => {}
+                Err(err) => error!("Quest deadline sweep failed: {:?}", err),
+            }
+        }
+    })
+}
@@ -0,0 +1,41 @@
+use std::{ sync::Arc, time::Duration };
+
+use tokio::{ task::JoinHandle, time::MissedTickBehavior };
+use tracing::{ error, info };
+
+use crate::{
+    application::{ caches::board_cache::BoardCache, usecases::quest_ops::QuestOpsUseCase },
+    infrastructure::postgres::{
+        postgres_connection::PgPoolSquad,
+        repositories::{ quest_ops::QuestOpsPostgres, quest_viewing::QuestVieweingPostgres },
+    },
+};
+
+/// Purges quests past the trash retention every `interval` until the returned
+/// task is aborted. Purging is idempotent, so instances need not coordinate.
+pub fn spawn(db_pool: Arc<PgPoolSquad>, board_cache: Arc<BoardCache>, interval: Duration) -> JoinHandle<()> {
+    let quest_ops_repository = QuestOpsPostgres::new(Arc::clone(&db_pool));
+    let quest_viewing_repository = QuestVieweingPostgres::new(db_pool);
+    let quest_ops_use_case = QuestOpsUseCase::new(
+        Arc::new(quest_ops_repository),
+        Arc::new(quest_viewing_repository),
+        board_cache
+    );
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+
+            match quest_ops_use_case.purge_trash().await {
+                Ok(quest_ids) if !quest_ids.is_empty() => {
+                    info!("Purged {} quests from the trash: {:?}", quest_ids.len(), quest_ids);
+                }
+                Ok(_) => {}
+                Err(err) => error!("Quest trash purge failed: {:?}", err),
+            }
+        }
+    })
+}
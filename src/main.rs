@@ -20,7 +20,7 @@ async fn main() {
 
     info!("Config loaded: {:?}", config);
 
-    let postgres_pool = match postgres_connection::establish_connection(&config.database.url) {
+    let postgres_pool = match postgres_connection::establish_connection(&config.database) {
         Ok(pool) => pool,
         Err(e) => {
             error!("Failed to connect to database: {:#}", e);